@@ -1,5 +1,9 @@
-use sauvequipeut::player;
-use std::io::{Read, Write};
+use sauvequipeut::codec::{FramedStream, DEFAULT_MAX_FRAME_SIZE};
+use sauvequipeut::protocol::{
+    ClientMessage, RegisterTeam, RegisterTeamResult, ServerMessage, SubscribePlayer,
+    SubscribePlayerResult,
+};
+use sauvequipeut::radar::{Cell, CellEntity, CellNature, Passage, RadarView};
 use std::net::{TcpListener, TcpStream};
 
 fn main() {
@@ -23,7 +27,7 @@ fn main() {
     }
 }
 
-fn handle_client(mut stream: TcpStream) {
+fn handle_client(stream: TcpStream) {
     match stream.peer_addr() {
         Ok(addr) => println!("Client connecté: {}", addr),
         Err(e) => {
@@ -32,30 +36,26 @@ fn handle_client(mut stream: TcpStream) {
         }
     }
 
-    let mut size_buffer = [0_u8; 4];
-    if let Err(e) = stream.read_exact(&mut size_buffer) {
-        eprintln!("Erreur lors de la lecture de la taille du message : {}", e);
-        return;
-    }
-    let message_size = u32::from_le_bytes(size_buffer) as usize;
-
-    let mut buffer = vec![0; message_size];
-    if let Err(e) = stream.read_exact(&mut buffer) {
-        eprintln!("Erreur lors de la lecture du message : {}", e);
-        return;
-    }
-
-    let received = String::from_utf8_lossy(&buffer).trim().to_string();
+    let mut connection = match FramedStream::new(stream, DEFAULT_MAX_FRAME_SIZE) {
+        Ok(connection) => connection,
+        Err(e) => {
+            eprintln!("Impossible de préparer la connexion : {}", e);
+            return;
+        }
+    };
 
-    let mut writing_buffer = vec![];
+    let received = match connection.reader.read_frame() {
+        Ok(frame) => String::from_utf8_lossy(frame).trim().to_string(),
+        Err(e) => {
+            eprintln!("Erreur lors de la lecture du message : {}", e);
+            return;
+        }
+    };
 
     //Client Team response
     if received == get_expected_step(1) {
-        writing_buffer.extend((get_response(1).len() as u32).to_le_bytes());
-        writing_buffer.extend(get_response(1).as_bytes());
-
-        println!("Reçu `{}`, réponse `{}`", received, get_response(2));
-        if let Err(e) = stream.write_all(&writing_buffer) {
+        println!("Reçu `{}`, réponse `{:?}`", received, get_response(1));
+        if let Err(e) = connection.writer.send(&get_response(1)) {
             eprintln!("Erreur lors de l'envoi de la réponse : {}", e);
             return;
         }
@@ -63,62 +63,40 @@ fn handle_client(mut stream: TcpStream) {
 
     //Client Player response
     if received == get_expected_step(2) {
-        writing_buffer.extend((get_response(2).len() as u32).to_le_bytes());
-        writing_buffer.extend(get_response(2).as_bytes());
-
-        println!("Reçu `{}`, réponse `{}`", received, get_response(2));
-        if let Err(e) = stream.write_all(&writing_buffer) {
+        println!("Reçu `{}`, réponse `{:?}`", received, get_response(2));
+        if let Err(e) = connection.writer.send(&get_response(2)) {
             eprintln!("Erreur lors de l'envoi de la réponse : {}", e);
             return;
         }
 
-        writing_buffer = vec![];
-
-        writing_buffer.extend((get_response(3).len() as u32).to_le_bytes());
-        writing_buffer.extend(get_response(3).as_bytes());
-
-        println!("Reçu `{}`, réponse `{}`", received, get_response(3));
-        if let Err(e) = stream.write_all(&writing_buffer) {
+        println!("Reçu `{}`, réponse `{:?}`", received, get_response(3));
+        if let Err(e) = connection.writer.send(&get_response(3)) {
             eprintln!("Erreur lors de l'envoi de la réponse : {}", e);
             return;
         }
 
-        let mut size_buffer = [0_u8; 4];
-        if let Err(e) = stream.read_exact(&mut size_buffer) {
-            eprintln!("Erreur lors de la lecture de la taille du message : {}", e);
-            return;
-        }
-        let message_size = u32::from_le_bytes(size_buffer) as usize;
-
-        let mut buffer = vec![0; message_size];
-        if let Err(e) = stream.read_exact(&mut buffer) {
-            eprintln!("Erreur lors de la lecture du message : {}", e);
-            return;
+        match connection.reader.read_frame() {
+            Ok(frame) => println!("Reçu `{}`", String::from_utf8_lossy(frame).trim()),
+            Err(e) => eprintln!("Erreur lors de la lecture du message : {}", e),
         }
-        let received = String::from_utf8_lossy(&buffer).trim().to_string();
-        println!("Reçu `{}`", received);
     }
 }
 
 fn get_expected_step(iteration: u8) -> String {
     match iteration {
-        1 => match serde_json::to_string(&player::RegisterTeam {
-            RegisterTeam: player::RegisterTeamBody {
-                name: "team_example".to_string(),
-            },
-        }) {
+        1 => match serde_json::to_string(&ClientMessage::RegisterTeam(RegisterTeam {
+            name: "team_example".to_string(),
+        })) {
             Ok(res) => res,
             Err(e) => {
                 eprintln!("Erreur de sérialisation de RegisterTeam : {}", e);
                 String::new()
             }
         },
-        2 => match serde_json::to_string(&player::SubscribePlayer {
-            SubscribePlayer: player::SubscribePlayerBody {
-                name: "player_1".to_string(),
-                registration_token: "abcd1234".to_string(),
-            },
-        }) {
+        2 => match serde_json::to_string(&ClientMessage::SubscribePlayer(SubscribePlayer {
+            name: "player_1".to_string(),
+            registration_token: "abcd1234".to_string(),
+        })) {
             Ok(res) => res,
             Err(e) => {
                 eprintln!("Erreur de sérialisation de SubscribePlayer : {}", e);
@@ -130,36 +108,38 @@ fn get_expected_step(iteration: u8) -> String {
     }
 }
 
-fn get_response(iteration: u8) -> String {
+fn get_response(iteration: u8) -> ServerMessage {
     match iteration {
-        1 => match serde_json::to_string(
-            &serde_json::json!({"RegisterTeamResult": player::RegisterTeamResult::Ok {
-                expected_players: 1,
-                registration_token: "abcd1234".to_string(),
-            }}),
-        ) {
-            Ok(res) => res,
-            Err(e) => {
-                eprintln!("Erreur de sérialisation de RegisterTeamResult : {}", e);
-                String::new()
-            }
-        },
-        2 => match serde_json::to_string(
-            &serde_json::json!({"SubscribePlayerResult": player::SubscribePlayerResult::Ok {}}),
-        ) {
-            Ok(res) => res,
-            Err(e) => {
-                eprintln!("Erreur de sérialisation de SubscribePlayerResult : {}", e);
-                String::new()
-            }
-        },
-        3 => match serde_json::to_string(&serde_json::json!({"RadarView": "zveKvsuL8a8aaaa"})) {
-            Ok(res) => res,
-            Err(e) => {
-                eprintln!("Erreur de sérialisation de RadarView : {}", e);
-                String::new()
-            }
-        },
-        _ => String::new(),
+        1 => ServerMessage::RegisterTeamResult(RegisterTeamResult::Ok {
+            expected_players: 1,
+            registration_token: "abcd1234".to_string(),
+        }),
+        2 => ServerMessage::SubscribePlayerResult(SubscribePlayerResult::Ok),
+        _ => ServerMessage::RadarView(sample_radar_view().encode()),
+    }
+}
+
+/// Vue radar de démonstration : le joueur est dans un couloir, la sortie juste devant lui.
+fn sample_radar_view() -> RadarView {
+    use Passage::{Open as O, Undefined as U, Wall as W};
+
+    let goal = Cell::Known {
+        nature: CellNature::Goal,
+        entity: CellEntity::None,
+    };
+    RadarView {
+        horizontal: [U, W, U, U, O, U, U, O, U, U, W, U],
+        vertical: [U, W, W, U, U, W, W, U, U, W, W, U],
+        cells: [
+            Cell::Undefined,
+            goal,
+            Cell::Undefined,
+            Cell::Undefined,
+            Cell::EMPTY,
+            Cell::Undefined,
+            Cell::Undefined,
+            Cell::EMPTY,
+            Cell::Undefined,
+        ],
     }
 }
@@ -0,0 +1,171 @@
+use crate::codec::FrameWriter;
+use crate::config::Config;
+use crate::game::GameState;
+use crate::player::{handle_secret_sum_modulo, PlayerState};
+use crate::protocol::Challenge;
+use std::collections::{BTreeMap, BTreeSet};
+use std::net::TcpStream;
+use std::sync::Arc;
+
+/// Ce dont dispose un solveur pour répondre à un challenge.
+pub struct ChallengeContext<'a> {
+    pub player_name: &'a str,
+    pub player_state: &'a PlayerState,
+    /// État partagé par toute l'équipe
+    pub game_state: &'a Arc<GameState>,
+    pub config: &'a Config,
+    pub writer: &'a mut FrameWriter<TcpStream>,
+}
+
+/// Résolution d'un type de challenge.
+///
+/// Un solveur est propre à un joueur et peut conserver un état entre la réception du
+/// challenge et sa fin.
+pub trait ChallengeSolver: Send {
+    /// Traite un challenge reçu du serveur.
+    fn solve(&mut self, challenge: &Challenge, context: &mut ChallengeContext);
+
+    /// Le serveur a refusé la dernière réponse (`InvalidChallengeSolution`).
+    fn retry(&mut self, _context: &mut ChallengeContext) {}
+
+    /// Le challenge est terminé : le joueur peut de nouveau se déplacer.
+    fn finish(&mut self, _context: &mut ChallengeContext) {}
+}
+
+/// Solveurs d'un joueur, indexés par type de challenge (voir [`Challenge::kind`]).
+pub struct ChallengeSolvers {
+    solvers: BTreeMap<String, Box<dyn ChallengeSolver>>,
+    /// Types des challenges en cours
+    active: BTreeSet<String>,
+}
+
+impl ChallengeSolvers {
+    /// Aucun solveur enregistré.
+    pub fn empty() -> Self {
+        Self {
+            solvers: BTreeMap::new(),
+            active: BTreeSet::new(),
+        }
+    }
+
+    /// Enregistre (ou remplace) le solveur du type `kind`.
+    pub fn register(&mut self, kind: &str, solver: Box<dyn ChallengeSolver>) -> &mut Self {
+        self.solvers.insert(kind.to_string(), solver);
+        self
+    }
+
+    /// Types de challenge pris en charge, par ordre alphabétique.
+    pub fn kinds(&self) -> impl Iterator<Item = &str> {
+        self.solvers.keys().map(String::as_str)
+    }
+
+    /// Transmet `challenge` à son solveur. Retourne `false` si son type n'est pas pris en charge.
+    pub fn solve(&mut self, challenge: &Challenge, context: &mut ChallengeContext) -> bool {
+        if let Challenge::Unsupported(_) = challenge {
+            return false;
+        }
+        let kind = challenge.kind();
+        let Some(solver) = self.solvers.get_mut(kind) else {
+            return false;
+        };
+        solver.solve(challenge, context);
+        self.active.insert(kind.to_string());
+        true
+    }
+
+    /// Transmet le refus d'une réponse aux solveurs des challenges en cours.
+    pub fn retry(&mut self, context: &mut ChallengeContext) {
+        for kind in &self.active {
+            if let Some(solver) = self.solvers.get_mut(kind) {
+                solver.retry(context);
+            }
+        }
+    }
+
+    /// Termine les challenges en cours.
+    pub fn finish(&mut self, context: &mut ChallengeContext) {
+        for kind in std::mem::take(&mut self.active) {
+            if let Some(solver) = self.solvers.get_mut(&kind) {
+                solver.finish(context);
+            }
+        }
+    }
+}
+
+impl Default for ChallengeSolvers {
+    /// Solveurs des challenges connus : `SecretSumModulo` et `SOS`.
+    fn default() -> Self {
+        let mut solvers = Self::empty();
+        solvers
+            .register("SecretSumModulo", Box::<SecretSumModuloSolver>::default())
+            .register("SOS", Box::new(SosSolver));
+        solvers
+    }
+}
+
+/// Somme des secrets de l'équipe modulo la valeur fournie, recalculée avec des secrets plus
+/// récents si le serveur refuse la réponse.
+#[derive(Debug, Default)]
+pub struct SecretSumModuloSolver {
+    /// Modulo du challenge en cours et numéro du dernier secret utilisé
+    pending: Option<(u64, u64)>,
+}
+
+impl SecretSumModuloSolver {
+    fn answer(&mut self, modulo: u64, after_seq: u64, context: &mut ChallengeContext) {
+        self.pending = handle_secret_sum_modulo(
+            context.writer,
+            context.player_name,
+            context.game_state,
+            &context.config.secret_wait,
+            modulo,
+            after_seq,
+        )
+        .map(|seq| (modulo, seq));
+    }
+}
+
+impl ChallengeSolver for SecretSumModuloSolver {
+    fn solve(&mut self, challenge: &Challenge, context: &mut ChallengeContext) {
+        if let Challenge::SecretSumModulo(modulo) = *challenge {
+            println!(
+                "[{}] SecretSumModulo challenge received with modulo {}",
+                context.player_name, modulo
+            );
+            self.answer(modulo, 0, context);
+        }
+    }
+
+    fn retry(&mut self, context: &mut ChallengeContext) {
+        if let Some((modulo, seq)) = self.pending {
+            println!(
+                "[{}] Réponse refusée, nouvel essai avec des secrets plus récents",
+                context.player_name
+            );
+            self.answer(modulo, seq, context);
+        }
+    }
+
+    fn finish(&mut self, _context: &mut ChallengeContext) {
+        self.pending = None;
+    }
+}
+
+/// Appel à l'aide : le joueur est bloqué jusqu'à ce qu'un coéquipier le rejoigne.
+#[derive(Debug, Default)]
+pub struct SosSolver;
+
+impl ChallengeSolver for SosSolver {
+    fn solve(&mut self, _challenge: &Challenge, context: &mut ChallengeContext) {
+        let position = context.player_state.confirmed_position();
+        let rescuers = context.game_state.raise_sos(context.player_name, position);
+        println!(
+            "[{}] 🆘 Challenge SOS : bloqué en {:?}, secouristes envoyés : {:?}",
+            context.player_name, position, rescuers
+        );
+    }
+
+    fn finish(&mut self, context: &mut ChallengeContext) {
+        context.game_state.resolve_sos(context.player_name);
+    }
+}
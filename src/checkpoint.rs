@@ -0,0 +1,36 @@
+use crate::player::SavedState;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Sauvegarde de la progression de l'équipe : état de chaque joueur et secrets partagés.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub players: HashMap<String, SavedState>,
+    pub secrets: HashMap<String, u64>,
+}
+
+impl Checkpoint {
+    /// Charge une sauvegarde depuis un fichier JSON.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let data = fs::read_to_string(path)
+            .map_err(|e| format!("Lecture de {} impossible : {}", path.display(), e))?;
+        serde_json::from_str(&data)
+            .map_err(|e| format!("Sauvegarde {} invalide : {}", path.display(), e))
+    }
+
+    /// Écrit la sauvegarde dans un fichier JSON.
+    ///
+    /// Le contenu est d'abord écrit dans un fichier temporaire puis renommé, pour ne jamais
+    /// laisser une sauvegarde tronquée en cas d'arrêt brutal.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let data = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Sérialisation de la sauvegarde impossible : {}", e))?;
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, data)
+            .map_err(|e| format!("Écriture de {} impossible : {}", tmp_path.display(), e))?;
+        fs::rename(&tmp_path, path)
+            .map_err(|e| format!("Remplacement de {} impossible : {}", path.display(), e))
+    }
+}
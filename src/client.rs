@@ -1,45 +1,39 @@
-use std::collections::HashMap;
-use std::net::TcpStream;
-use std::sync::{Arc, Mutex};
+use crate::checkpoint::Checkpoint;
+use crate::codec::FramedStream;
+use crate::config::{Config, ConfigError};
+use crate::game::{save_progress, start_game_loop, GameLoopExit, GameState};
+use crate::navigation::{ExitRouting, NavigationStrategy, RescueRouting, StrategyRegistry};
+use crate::player;
+use crate::player::{MovementLog, Orientation, PlayerState, Position, SubscribeError};
+use std::path::Path;
+use std::sync::Arc;
 use std::thread;
-use crate::{game, player};
-use crate::game::start_game_loop;
 
+/// Lance un thread par joueur attendu, chacun avec sa propre instance de la stratégie
+/// `config.navigation_mode`, et attend leur fin.
+///
+/// Quelle que soit la stratégie, les joueurs rejoignent la sortie dès qu'elle est connue.
 pub fn start_player_threads(
-    server_address: &str,
+    config: Arc<Config>,
+    registry: &StrategyRegistry,
     registration_token: String,
     expected_players: u8
-) {
+) -> Result<(), ConfigError> {
     let mut handles = vec![];
-    let game_state = Arc::new(game::GameState {
-        secrets: Mutex::new(HashMap::new()),
-    });
+    let game_state = Arc::new(initial_game_state(&config));
 
     for i in 1..=expected_players {
         let player_name = format!("player_{}", i);
         let token = registration_token.clone();
-        let server = server_address.to_string();
+        let exploration = ExitRouting::new(registry.create(&config.navigation_mode)?);
+        let mut strategy = RescueRouting::new(Box::new(exploration));
 
         let handle = thread::spawn({
             let game_state = game_state.clone();
+            let config = config.clone();
             move || {
                 println!("Thread started for {}", player_name);
-
-                let mut stream = match TcpStream::connect(&server) {
-                    Ok(s) => s,
-                    Err(e) => {
-                        eprintln!("Failed to connect for {}: {}", player_name, e);
-                        return;
-                    }
-                };
-
-                if let Err(e) = player::subscribe_player(&mut stream, &player_name, &token) {
-                    eprintln!("Failed to register player {}: {}", player_name, e);
-                    return;
-                }
-
-                println!("Player {} registered successfully!", player_name);
-                start_game_loop(&mut stream, &player_name, game_state);
+                supervise_player(&config, &player_name, &token, &game_state, &mut strategy);
             }
         });
 
@@ -51,4 +45,184 @@ pub fn start_player_threads(
             eprintln!("A thread panicked: {:?}", e);
         }
     }
+    Ok(())
+}
+
+/// État partagé de départ : repris depuis la sauvegarde si `config.resume_progress` est actif.
+fn initial_game_state(config: &Config) -> GameState {
+    if !config.resume_progress {
+        return GameState::new(config.separate_spawns);
+    }
+
+    match Checkpoint::load(Path::new(&config.checkpoint_path)) {
+        Ok(checkpoint) => {
+            println!(
+                "Sauvegarde {} chargée : {} joueur(s), {} secret(s)",
+                config.checkpoint_path,
+                checkpoint.players.len(),
+                checkpoint.secrets.len()
+            );
+            GameState::from_checkpoint(checkpoint, config.separate_spawns)
+        }
+        Err(e) => {
+            eprintln!("Reprise impossible, nouvelle partie : {}", e);
+            GameState::new(config.separate_spawns)
+        }
+    }
+}
+
+/// Fait jouer un joueur jusqu'à la fin de la partie, en le reconnectant après une coupure.
+///
+/// L'état du joueur (position, orientation, cases visitées) et sa stratégie sont conservés
+/// d'une connexion à l'autre : l'exploration reprend là où elle s'était arrêtée. Le superviseur abandonne
+/// lorsque la réinscription échoue malgré la politique `config.subscribe_retry`.
+pub fn supervise_player(
+    config: &Config,
+    player_name: &str,
+    registration_token: &str,
+    game_state: &Arc<GameState>,
+    strategy: &mut dyn NavigationStrategy,
+) {
+    let movement_logger = MovementLog {
+        player_name: player_name.to_string(),
+    };
+    let mut player_state = match game_state.saved_player(player_name) {
+        Some(saved) => {
+            println!(
+                "[{}] Reprise depuis la sauvegarde en {:?}",
+                player_name, saved.position
+            );
+            PlayerState::restore(&saved)
+        }
+        None => {
+            if let Err(e) = movement_logger.reset_log() {
+                eprintln!(
+                    "[{}] Erreur lors de la réinitialisation du log : {}",
+                    player_name, e
+                );
+            }
+            PlayerState::new(Position::new(0, 0), Orientation::North)
+        }
+    };
+
+    let mut sessions = 0;
+    // Sessions consécutives terminées sans aucun déplacement confirmé
+    let mut failed_sessions = 0;
+    loop {
+        let mut connection = match connect_and_subscribe(config, player_name, registration_token) {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("Failed to register player {}: {}", player_name, e);
+                break;
+            }
+        };
+
+        if sessions == 0 {
+            println!("Player {} registered successfully!", player_name);
+        } else {
+            println!(
+                "[{}] Reconnecté, reprise en {:?} orienté {:?} ({} cases visitées)",
+                player_name,
+                player_state.position,
+                player_state.orientation,
+                player_state.visited.len()
+            );
+        }
+        sessions += 1;
+        game_state.register_player(player_name);
+
+        match start_game_loop(
+            &mut connection,
+            player_name,
+            game_state,
+            config,
+            &mut player_state,
+            &movement_logger,
+            strategy,
+        ) {
+            GameLoopExit::Disconnected {
+                error,
+                confirmed_moves,
+            } => {
+                eprintln!("[{}] Connexion perdue ({})", player_name, error);
+                // Sans confirmation du serveur, on considère que le dernier déplacement n'a pas eu lieu
+                if let Some(pending) = player_state.rollback_move() {
+                    println!(
+                        "[{}] Déplacement {} non confirmé annulé",
+                        player_name, pending.movement
+                    );
+                }
+                if config.save_progress {
+                    save_progress(game_state, config, player_name, &player_state);
+                }
+
+                // Une connexion qui tombe sans progrès ne doit pas être relancée en boucle
+                failed_sessions = if confirmed_moves == 0 {
+                    failed_sessions + 1
+                } else {
+                    0
+                };
+                let policy = &config.subscribe_retry;
+                if failed_sessions >= policy.max_attempts {
+                    eprintln!(
+                        "[{}] Abandon après {} sessions sans progrès",
+                        player_name, failed_sessions
+                    );
+                    break;
+                }
+                if failed_sessions > 0 {
+                    thread::sleep(policy.backoff(failed_sessions));
+                }
+                println!("[{}] Tentative de reconnexion", player_name);
+            }
+            GameLoopExit::Finished => {
+                println!(
+                    "[{}] Plus aucun déplacement à jouer, fin de partie",
+                    player_name
+                );
+                break;
+            }
+        }
+    }
+
+    game_state.release_player(player_name);
+    if config.save_progress {
+        save_progress(game_state, config, player_name, &player_state);
+    }
+}
+
+/// Ouvre une connexion et inscrit le joueur, en réessayant selon `config.subscribe_retry`.
+///
+/// Chaque tentative utilise une nouvelle connexion. Les refus définitifs du serveur
+/// (jeton invalide, équipe complète, ...) ne sont pas réessayés.
+pub fn connect_and_subscribe(
+    config: &Config,
+    player_name: &str,
+    registration_token: &str,
+) -> Result<FramedStream, SubscribeError> {
+    let policy = &config.subscribe_retry;
+    let mut attempt = 1;
+
+    loop {
+        let result = FramedStream::connect(&config.server_address, config.max_frame_size)
+            .map_err(SubscribeError::from)
+            .and_then(|mut connection| {
+                player::subscribe_player(&mut connection, player_name, registration_token)?;
+                Ok(connection)
+            });
+
+        match result {
+            Ok(connection) => return Ok(connection),
+            Err(e) if e.is_retryable() && attempt < policy.max_attempts => {
+                let delay = policy.backoff(attempt);
+                eprintln!(
+                    "[{}] Tentative d'inscription {}/{} échouée : {}. Nouvel essai dans {:?}",
+                    player_name, attempt, policy.max_attempts, e, delay
+                );
+                thread::sleep(delay);
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
 }
@@ -0,0 +1,172 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+
+/// Taille maximale par défaut d'une trame (1 Mio), en-tête exclu.
+pub const DEFAULT_MAX_FRAME_SIZE: usize = 1_048_576;
+
+/// Erreurs de lecture ou d'écriture d'une trame.
+#[derive(Debug)]
+pub enum FrameError {
+    /// Erreur d'entrée/sortie sur le flux sous-jacent.
+    Io(io::Error),
+    /// La trame annoncée ou à envoyer dépasse la taille maximale autorisée.
+    TooLarge { size: usize, max: usize },
+    /// Le message n'a pas pu être sérialisé en JSON.
+    Serialize(serde_json::Error),
+    /// Le contenu de la trame n'est pas le JSON attendu.
+    Deserialize {
+        error: serde_json::Error,
+        frame: String,
+    },
+}
+
+impl fmt::Display for FrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrameError::Io(e) => write!(f, "Erreur d'entrée/sortie : {}", e),
+            FrameError::TooLarge { size, max } => {
+                write!(f, "Trame trop grande : {} octets (maximum {})", size, max)
+            }
+            FrameError::Serialize(e) => write!(f, "Échec de la sérialisation : {}", e),
+            FrameError::Deserialize { error, frame } => {
+                write!(f, "Trame invalide ({}) : {}", error, frame)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FrameError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FrameError::Io(e) => Some(e),
+            FrameError::Serialize(e) | FrameError::Deserialize { error: e, .. } => Some(e),
+            FrameError::TooLarge { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for FrameError {
+    fn from(e: io::Error) -> Self {
+        FrameError::Io(e)
+    }
+}
+
+/// Lit des trames `taille (u32 little-endian) + JSON` depuis un flux.
+///
+/// Le tampon de lecture est réutilisé d'une trame à l'autre.
+pub struct FrameReader<R> {
+    inner: R,
+    buffer: Vec<u8>,
+    max_frame_size: usize,
+}
+
+impl<R: Read> FrameReader<R> {
+    pub fn new(inner: R, max_frame_size: usize) -> Self {
+        Self {
+            inner,
+            buffer: Vec::new(),
+            max_frame_size,
+        }
+    }
+
+    /// Lit la trame suivante et renvoie son contenu brut.
+    ///
+    /// La taille annoncée est vérifiée avant toute allocation.
+    pub fn read_frame(&mut self) -> Result<&[u8], FrameError> {
+        let mut size_buffer = [0; 4];
+        self.inner.read_exact(&mut size_buffer)?;
+        let size = u32::from_le_bytes(size_buffer) as usize;
+        if size > self.max_frame_size {
+            return Err(FrameError::TooLarge {
+                size,
+                max: self.max_frame_size,
+            });
+        }
+
+        self.buffer.resize(size, 0);
+        self.inner.read_exact(&mut self.buffer)?;
+        Ok(&self.buffer)
+    }
+
+    /// Lit la trame suivante et la désérialise.
+    pub fn recv<T: DeserializeOwned>(&mut self) -> Result<T, FrameError> {
+        let frame = self.read_frame()?;
+        serde_json::from_slice(frame).map_err(|error| FrameError::Deserialize {
+            error,
+            frame: String::from_utf8_lossy(frame).into_owned(),
+        })
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+}
+
+/// Écrit des trames `taille (u32 little-endian) + JSON` sur un flux.
+///
+/// Le tampon d'écriture est réutilisé d'une trame à l'autre.
+pub struct FrameWriter<W> {
+    inner: W,
+    buffer: Vec<u8>,
+    max_frame_size: usize,
+}
+
+impl<W: Write> FrameWriter<W> {
+    pub fn new(inner: W, max_frame_size: usize) -> Self {
+        Self {
+            inner,
+            buffer: Vec::new(),
+            max_frame_size,
+        }
+    }
+
+    /// Sérialise `message` en JSON et l'envoie dans une seule trame.
+    pub fn send<T: Serialize>(&mut self, message: &T) -> Result<(), FrameError> {
+        self.buffer.clear();
+        self.buffer.extend_from_slice(&[0; 4]);
+        serde_json::to_writer(&mut self.buffer, message).map_err(FrameError::Serialize)?;
+
+        let size = self.buffer.len() - 4;
+        if size > self.max_frame_size || size > u32::MAX as usize {
+            return Err(FrameError::TooLarge {
+                size,
+                max: self.max_frame_size,
+            });
+        }
+
+        self.buffer[..4].copy_from_slice(&(size as u32).to_le_bytes());
+        self.inner.write_all(&self.buffer)?;
+        self.inner.flush()?;
+        Ok(())
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+}
+
+/// Connexion TCP découpée en trames dans les deux sens.
+pub struct FramedStream {
+    pub reader: FrameReader<TcpStream>,
+    pub writer: FrameWriter<TcpStream>,
+}
+
+impl FramedStream {
+    /// Ouvre une connexion vers `address`.
+    pub fn connect(address: &str, max_frame_size: usize) -> Result<Self, FrameError> {
+        let stream = TcpStream::connect(address)?;
+        Ok(Self::new(stream, max_frame_size)?)
+    }
+
+    /// Découpe un flux déjà connecté en une moitié lecture et une moitié écriture.
+    pub fn new(stream: TcpStream, max_frame_size: usize) -> io::Result<Self> {
+        let write_half = stream.try_clone()?;
+        Ok(Self {
+            reader: FrameReader::new(stream, max_frame_size),
+            writer: FrameWriter::new(write_half, max_frame_size),
+        })
+    }
+}
@@ -1,6 +1,10 @@
+use crate::codec::DEFAULT_MAX_FRAME_SIZE;
+use crate::navigation::StrategyRegistry;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::fs;
 use std::io::{self};
+use std::time::Duration;
 
 /// Structure de configuration du client, chargée depuis `config.toml`
 #[derive(Debug, Deserialize, Serialize)]
@@ -9,8 +13,127 @@ pub struct Config {
     pub team_name: String,
     pub navigation_mode: String,
     pub save_progress: bool,
+    /// Fichier de sauvegarde de la progression
+    #[serde(default = "default_checkpoint_path")]
+    pub checkpoint_path: String,
+    /// Nombre de déplacements confirmés entre deux sauvegardes d'un joueur
+    #[serde(default = "default_checkpoint_interval")]
+    pub checkpoint_interval: u32,
+    /// Reprendre la partie depuis la sauvegarde existante au démarrage
+    #[serde(default)]
+    pub resume_progress: bool,
+    /// Taille maximale (en octets) d'une trame échangée avec le serveur
+    #[serde(default = "default_max_frame_size")]
+    pub max_frame_size: usize,
+    /// Politique de nouvelle tentative pour l'inscription des joueurs
+    #[serde(default)]
+    pub subscribe_retry: RetryPolicy,
+    /// Attente des secrets frais avant de répondre à un challenge `SecretSumModulo`
+    #[serde(default)]
+    pub secret_wait: SecretWaitPolicy,
+    /// Les joueurs peuvent démarrer sur des cellules différentes : chacun garde sa propre
+    /// carte jusqu'à l'avoir située sur la grille grâce à l'indice `GridSize`
+    #[serde(default)]
+    pub separate_spawns: bool,
 }
 
+fn default_checkpoint_path() -> String {
+    "progress.json".to_string()
+}
+
+fn default_checkpoint_interval() -> u32 {
+    10
+}
+
+fn default_max_frame_size() -> usize {
+    DEFAULT_MAX_FRAME_SIZE
+}
+
+/// Politique de nouvelles tentatives avec attente exponentielle entre deux essais.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// Nombre total de tentatives, la première comprise
+    pub max_attempts: u32,
+    /// Attente avant la deuxième tentative, en millisecondes
+    pub initial_backoff_ms: u64,
+    /// Attente maximale entre deux tentatives, en millisecondes
+    pub max_backoff_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff_ms: 500,
+            max_backoff_ms: 8_000,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Durée d'attente après l'échec de la tentative numéro `attempt` (à partir de 1).
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u64.saturating_pow(attempt.saturating_sub(1));
+        let delay = self.initial_backoff_ms.saturating_mul(factor);
+        Duration::from_millis(delay.min(self.max_backoff_ms))
+    }
+}
+
+/// Attente des secrets de tous les joueurs inscrits avant de résoudre `SecretSumModulo`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SecretWaitPolicy {
+    /// Attente maximale, en millisecondes ; au-delà, la réponse utilise les secrets disponibles
+    pub timeout_ms: u64,
+    /// Âge maximal, en millisecondes, d'un secret considéré comme frais
+    pub max_age_ms: u64,
+}
+
+impl Default for SecretWaitPolicy {
+    fn default() -> Self {
+        Self {
+            timeout_ms: 2_000,
+            max_age_ms: 5_000,
+        }
+    }
+}
+
+impl SecretWaitPolicy {
+    pub fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_ms)
+    }
+
+    pub fn max_age(&self) -> Duration {
+        Duration::from_millis(self.max_age_ms)
+    }
+}
+
+/// Erreurs de configuration détectées au démarrage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    /// `navigation_mode` ne correspond à aucune stratégie enregistrée.
+    UnknownNavigationMode {
+        mode: String,
+        available: Vec<String>,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::UnknownNavigationMode { mode, available } => write!(
+                f,
+                "Mode de navigation inconnu : {} (disponibles : {})",
+                mode,
+                available.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
 impl Config {
     /// Charge la configuration depuis `config.toml`
     pub fn load() -> Result<Self, io::Error> {
@@ -18,4 +141,9 @@ impl Config {
         let config: Config = toml::from_str(&config_data).expect("Erreur lors du parsing TOML");
         Ok(config)
     }
+
+    /// Vérifie que la configuration est utilisable avec les stratégies de `registry`.
+    pub fn validate(&self, registry: &StrategyRegistry) -> Result<(), ConfigError> {
+        registry.create(&self.navigation_mode).map(|_| ())
+    }
 }
@@ -0,0 +1,82 @@
+use crate::map::MazeMap;
+use crate::planner::bfs_avoiding;
+use crate::player::Position;
+use std::collections::{HashMap, HashSet};
+
+/// Répartit les cellules frontières de la carte entre les joueurs de l'équipe, pour que
+/// chacun explore une zone différente.
+///
+/// L'attribution est gloutonne : un joueur sans objectif reçoit la frontière la plus proche
+/// (en longueur de chemin) qui n'est pas déjà attribuée à un coéquipier.
+#[derive(Debug, Default)]
+pub struct Coordinator {
+    targets: HashMap<String, Position>,
+}
+
+impl Coordinator {
+    /// Frontière actuellement attribuée à `player_name`.
+    pub fn target(&self, player_name: &str) -> Option<Position> {
+        self.targets.get(player_name).copied()
+    }
+
+    /// Libère la frontière attribuée à `player_name`, par exemple quand il quitte la partie.
+    pub fn release(&mut self, player_name: &str) -> Option<Position> {
+        self.targets.remove(player_name)
+    }
+
+    /// Chemin de `position` vers la frontière attribuée à `player_name`, sans passer par
+    /// `blocked`.
+    ///
+    /// L'objectif est conservé tant qu'il reste une frontière atteignable ; sinon une nouvelle
+    /// frontière est attribuée. Si toutes sont déjà prises, le joueur rejoint la plus proche.
+    /// Retourne `None` lorsqu'il n'y a plus aucune frontière atteignable.
+    pub fn assign(
+        &mut self,
+        map: &MazeMap,
+        player_name: &str,
+        position: Position,
+        blocked: &HashSet<Position>,
+    ) -> Option<Vec<Position>> {
+        if let Some(target) = self.target(player_name) {
+            if target != position && map.is_frontier(target) {
+                if let Some(path) = bfs_avoiding(map, position, blocked, |cell| cell == target) {
+                    return Some(path);
+                }
+                println!(
+                    "[{}] Frontière {:?} devenue inaccessible",
+                    player_name, target
+                );
+            } else {
+                println!("[{}] Frontière {:?} explorée", player_name, target);
+            }
+            self.targets.remove(player_name);
+        }
+
+        let taken: HashSet<Position> = self
+            .targets
+            .iter()
+            .filter(|(name, _)| name.as_str() != player_name)
+            .map(|(_, target)| *target)
+            .collect();
+        let is_frontier = |cell: Position| cell != position && map.is_frontier(cell);
+        let path = bfs_avoiding(map, position, blocked, |cell| {
+            is_frontier(cell) && !taken.contains(&cell)
+        })
+        .or_else(|| bfs_avoiding(map, position, blocked, is_frontier))?;
+
+        let target = *path.last()?;
+        println!(
+            "[{}] Frontière attribuée : {:?} ({} pas{})",
+            player_name,
+            target,
+            path.len() - 1,
+            if taken.contains(&target) {
+                ", partagée"
+            } else {
+                ""
+            }
+        );
+        self.targets.insert(player_name.to_string(), target);
+        Some(path)
+    }
+}
@@ -0,0 +1,142 @@
+use crate::player::Position;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::Add;
+use std::str::FromStr;
+
+/// Direction d'un déplacement, relative à l'orientation courante du joueur.
+///
+/// Sérialisée telle qu'attendue par l'action `MoveTo` du protocole (`"Front"`, `"Right"`, ...).
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RelativeDirection {
+    Front,
+    Right,
+    Back,
+    Left,
+}
+
+impl RelativeDirection {
+    /// Les quatre directions, dans le sens horaire à partir de l'avant.
+    pub const ALL: [RelativeDirection; 4] = [
+        RelativeDirection::Front,
+        RelativeDirection::Right,
+        RelativeDirection::Back,
+        RelativeDirection::Left,
+    ];
+
+    /// Nombre de quarts de tour dans le sens horaire.
+    fn quarter_turns(self) -> u8 {
+        self as u8
+    }
+
+    fn from_quarter_turns(turns: u8) -> Self {
+        Self::ALL[(turns % 4) as usize]
+    }
+
+    /// Enchaîne deux rotations : `Right.compose(Right) == Back`.
+    pub fn compose(self, other: RelativeDirection) -> RelativeDirection {
+        Self::from_quarter_turns(self.quarter_turns() + other.quarter_turns())
+    }
+
+    /// Direction opposée : `Left.invert() == Right`.
+    pub fn invert(self) -> RelativeDirection {
+        self.compose(RelativeDirection::Back)
+    }
+}
+
+impl fmt::Display for RelativeDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+impl FromStr for RelativeDirection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Front" => Ok(RelativeDirection::Front),
+            "Right" => Ok(RelativeDirection::Right),
+            "Back" => Ok(RelativeDirection::Back),
+            "Left" => Ok(RelativeDirection::Left),
+            _ => Err(format!("Direction inconnue : {}", s)),
+        }
+    }
+}
+
+/// Direction absolue dans le repère de la carte (le nord vers les `y` décroissants).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CardinalDirection {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl CardinalDirection {
+    /// Les quatre directions, dans le sens horaire à partir du nord.
+    pub const ALL: [CardinalDirection; 4] = [
+        CardinalDirection::North,
+        CardinalDirection::East,
+        CardinalDirection::South,
+        CardinalDirection::West,
+    ];
+
+    /// Direction absolue obtenue en tournant de `turn` depuis cette direction :
+    /// `North.rotate(Right) == East`.
+    pub fn rotate(self, turn: RelativeDirection) -> CardinalDirection {
+        Self::ALL[(self as usize + turn.quarter_turns() as usize) % 4]
+    }
+
+    pub fn opposite(self) -> CardinalDirection {
+        self.rotate(RelativeDirection::Back)
+    }
+
+    /// Direction relative à prendre, en faisant face à `facing`, pour aller vers `self` :
+    /// `East.relative_to(North) == Right`.
+    pub fn relative_to(self, facing: CardinalDirection) -> RelativeDirection {
+        RelativeDirection::from_quarter_turns((self as u8 + 4 - facing as u8) % 4)
+    }
+
+    /// Cap en degrés, dans le sens horaire à partir du nord.
+    pub fn degrees(self) -> f32 {
+        self as u8 as f32 * 90.0
+    }
+
+    /// Décalage `(dx, dy)` d'une case dans cette direction.
+    pub fn offset(self) -> (i32, i32) {
+        match self {
+            CardinalDirection::North => (0, -1),
+            CardinalDirection::East => (1, 0),
+            CardinalDirection::South => (0, 1),
+            CardinalDirection::West => (-1, 0),
+        }
+    }
+}
+
+/// Cap absolu (degrés dans le sens horaire à partir du nord, dans `[0, 360)`) d'un angle
+/// `angle` mesuré dans le sens horaire à partir de l'avant d'un joueur orienté `orientation`.
+pub fn absolute_bearing(orientation: CardinalDirection, angle: f32) -> f32 {
+    (orientation.degrees() + angle).rem_euclid(360.0)
+}
+
+/// Vecteur unitaire `(dx, dy)` d'un cap absolu, dans le repère de la carte (`y` vers le sud).
+pub fn bearing_vector(bearing: f32) -> (f64, f64) {
+    let radians = (bearing as f64).to_radians();
+    (radians.sin(), -radians.cos())
+}
+
+impl fmt::Display for CardinalDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+impl Add<CardinalDirection> for Position {
+    type Output = Position;
+
+    fn add(self, direction: CardinalDirection) -> Position {
+        let (dx, dy) = direction.offset();
+        Position::new(self.x() + dx, self.y() + dy)
+    }
+}
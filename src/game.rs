@@ -1,120 +1,795 @@
-use crate::player::{display_radar_view, handle_secret_sum_modulo, move_player, process_blocks, random_decide_move, send_move_action, tremaux_decide_move, MovementLog, Orientation, PlayerState, Position};
-use crate::utils::decode_b64;
-use std::collections::HashMap;
-use std::io::Read;
-use std::net::TcpStream;
-use std::sync::{Arc, Mutex};
-use crate::config::Config;
+use crate::challenge::{ChallengeContext, ChallengeSolvers};
+use crate::checkpoint::Checkpoint;
+use crate::codec::{FrameError, FramedStream};
+use crate::config::{Config, SecretWaitPolicy};
+use crate::coordinator::Coordinator;
+use crate::direction::absolute_bearing;
+use crate::map::GridSize;
+use crate::navigation::{ActionResult, NavigationContext, NavigationStrategy};
+use crate::planner::bfs_avoiding;
+use crate::player::{
+    display_radar_view, move_player, send_move_action, MovementLog, PlayerState, Position,
+    SavedState,
+};
+use crate::protocol::{parse_server_message, ActionError, Hint, ServerMessage};
+use crate::radar::RadarView;
+use crate::rescue::Rescue;
+use crate::secrets::{SecretSnapshot, SecretStore};
+use crate::team_map::TeamMap;
+use crate::triangulation::{CompassReading, GoalEstimate, Triangulation};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 
+#[derive(Default)]
 pub struct GameState {
-    pub secrets: Mutex<HashMap<String, u64>>, // Stocke les secrets des joueurs
+    /// Derniers secrets reçus par les joueurs
+    pub secrets: Mutex<SecretStore>,
+    /// Signalé à chaque nouveau secret
+    pub secrets_updated: Condvar,
+    /// Dernier état sauvegardé de chaque joueur
+    pub saved_players: Mutex<HashMap<String, SavedState>>,
+    /// Cartes du labyrinthe construites à partir des vues radar des joueurs
+    pub map: Mutex<TeamMap>,
+    /// Répartition des frontières de la carte commune entre les joueurs alignés
+    pub coordinator: Mutex<Coordinator>,
+    /// Relevés de boussole des joueurs alignés et position estimée de la sortie
+    pub triangulation: Mutex<Triangulation>,
+    /// Relevés de boussole des joueurs pas encore alignés, dans leur propre repère
+    pub local_triangulations: Mutex<HashMap<String, Triangulation>>,
+    /// Appels SOS en cours et coéquipiers envoyés à la rescousse
+    pub rescue: Mutex<Rescue>,
+    /// Nombre de challenges reçus sans solveur, par type
+    pub unsupported_challenges: Mutex<HashMap<String, u32>>,
 }
 
-pub fn start_game_loop(
-    stream: &mut TcpStream,
-    player_name: &str,
-    game_state: Arc<GameState>,
-) {
-    let config = Arc::new(Config::load().expect("Erreur chargement config"));
-    let mut player_state = PlayerState {
-        position: Position::new(0, 0),
-        visited: HashMap::new(),
-        last_direction: None, // Nouvelle initialisation
-        orientation: Orientation::North,
-    };
-    let movement_logger = MovementLog {
-        player_name: player_name.to_string(),
-    };
-    if let Err(e) = movement_logger.reset_log() {
-        eprintln!("[{}] Erreur lors de la réinitialisation du log : {}", player_name, e);
+impl GameState {
+    /// État partagé vide. Avec `separate_spawns`, chaque joueur garde sa propre carte jusqu'à
+    /// ce que l'indice `GridSize` la situe sur la grille (voir [`TeamMap`]).
+    pub fn new(separate_spawns: bool) -> Self {
+        Self {
+            map: Mutex::new(TeamMap::new(separate_spawns)),
+            ..Default::default()
+        }
     }
-    loop {
-        println!("[{}] Waiting for message...", player_name);
 
-        let mut size_buffer = [0; 4];
-        if let Err(e) = stream.read_exact(&mut size_buffer) {
-            eprintln!("[{}] Failed to read message size: {}", player_name, e);
-            break;
+    /// Reconstruit l'état partagé à partir d'une sauvegarde.
+    pub fn from_checkpoint(checkpoint: Checkpoint, separate_spawns: bool) -> Self {
+        Self {
+            secrets: Mutex::new(SecretStore::from_values(checkpoint.secrets)),
+            saved_players: Mutex::new(checkpoint.players),
+            ..Self::new(separate_spawns)
         }
-        let message_size = u32::from_le_bytes(size_buffer) as usize;
+    }
 
-        let mut response_buffer = vec![0; message_size];
-        if let Err(e) = stream.read_exact(&mut response_buffer) {
-            eprintln!("[{}] Failed to read message: {}", player_name, e);
-            break;
+    /// Intègre la vue radar d'un joueur dans sa carte.
+    ///
+    /// Retourne la position de la sortie, dans le repère du joueur, si cette vue vient de
+    /// la révéler.
+    pub fn integrate_radar(
+        &self,
+        player_name: &str,
+        radar: &RadarView,
+        player_state: &PlayerState,
+    ) -> Option<Position> {
+        let mut map = self.map.lock().ok()?;
+        let goal = map.integrate(
+            player_name,
+            radar,
+            player_state.position,
+            player_state.orientation,
+        );
+        self.align_players(&mut map);
+        goal
+    }
+
+    /// Enregistre les dimensions du labyrinthe. Retourne `true` si elles étaient inconnues.
+    pub fn set_grid_size(&self, grid_size: GridSize) -> bool {
+        let Ok(mut map) = self.map.lock() else {
+            return false;
+        };
+        let changed = map.set_grid_size(grid_size);
+        self.align_players(&mut map);
+        changed
+    }
+
+    /// Aligne sur la carte commune les joueurs qui ont situé l'origine de la grille, et
+    /// reporte leurs relevés de boussole dans la triangulation commune.
+    ///
+    /// Les verrous sont pris dans l'ordre carte, triangulation commune puis triangulations locales.
+    fn align_players(&self, map: &mut TeamMap) {
+        for (player_name, frame) in map.align() {
+            println!(
+                "🗺️ {} situé sur la grille : sa carte rejoint celle de l'équipe (décalage {:?})",
+                player_name,
+                frame.offset()
+            );
+            let (Ok(mut triangulation), Ok(mut local)) =
+                (self.triangulation.lock(), self.local_triangulations.lock())
+            else {
+                continue;
+            };
+            for reading in local
+                .remove(&player_name)
+                .iter()
+                .flat_map(|local| local.readings())
+            {
+                triangulation.record(CompassReading {
+                    position: frame.to_map(reading.position),
+                    ..reading.clone()
+                });
+            }
         }
+    }
 
-        let response: Result<serde_json::Value, _> = serde_json::from_slice(&response_buffer);
-        match response {
-            Ok(msg) => {
-                if let Some(radar_view) = msg.get("RadarView") {
-                    if let Some(encoded_view) = radar_view.as_str() {
-                        println!("RadarView: {}", encoded_view);
-                        match decode_b64(encoded_view) {
-                            Ok(decoded_data) => {
-                                println!("[{}] Decoded RadarView data: {}", player_name, decoded_data);
+    /// La carte de `player_name` a été entièrement explorée sans trouver de sortie.
+    pub fn exploration_exhausted(&self, player_name: &str) -> bool {
+        self.map
+            .lock()
+            .is_ok_and(|map| map.is_exhausted(player_name))
+    }
 
-                                let (horizontal, vertical, cells) = process_blocks(&decoded_data);
+    /// Chemin vers la prochaine frontière de la carte de `player_name`.
+    ///
+    /// Sur la carte commune, la frontière est attribuée par le coordinateur ; sur sa propre
+    /// carte, le joueur rejoint la plus proche. Les verrous sont toujours pris dans l'ordre
+    /// carte puis coordinateur.
+    pub fn frontier_path(
+        &self,
+        player_name: &str,
+        position: Position,
+        blocked: &HashSet<Position>,
+    ) -> Option<Vec<Position>> {
+        let maps = self.map.lock().ok()?;
+        let (map, frame) = maps.view(player_name)?;
+        let start = frame.to_map(position);
+        let blocked = frame.cells_to_map(blocked);
+        let path = if maps.frame(player_name).is_some() {
+            self.coordinator
+                .lock()
+                .ok()?
+                .assign(map, player_name, start, &blocked)?
+        } else {
+            bfs_avoiding(map, start, &blocked, |cell| {
+                cell != start && map.is_frontier(cell)
+            })?
+        };
+        Some(frame.path_to_player(path))
+    }
 
-                                println!("\nBlocs décodés :");
-                                println!("[{}] Passages horizontaux : {:?}", player_name, horizontal);
-                                println!("[{}] Passages verticaux : {:?}", player_name, vertical);
-                                println!("[{}] Cellules : {:?}", player_name, cells);
+    /// Une frontière de la carte de `player_name` est atteignable depuis `position` par des
+    /// passages connus, en ignorant les cellules momentanément bloquées.
+    pub fn frontier_reachable(&self, player_name: &str, position: Position) -> bool {
+        self.map.lock().is_ok_and(|maps| {
+            maps.view(player_name).is_some_and(|(map, frame)| {
+                let start = frame.to_map(position);
+                bfs_avoiding(map, start, &HashSet::new(), |cell| {
+                    cell != start && map.is_frontier(cell)
+                })
+                .is_some()
+            })
+        })
+    }
+
+    /// Plus court chemin connu de `position` jusqu'à la sortie, si elle figure sur la carte
+    /// de `player_name`.
+    pub fn exit_path(
+        &self,
+        player_name: &str,
+        position: Position,
+        blocked: &HashSet<Position>,
+    ) -> Option<Vec<Position>> {
+        let maps = self.map.lock().ok()?;
+        let (map, frame) = maps.view(player_name)?;
+        let goal = map.goal()?;
+        let path = bfs_avoiding(
+            map,
+            frame.to_map(position),
+            &frame.cells_to_map(blocked),
+            |cell| cell == goal,
+        )?;
+        Some(frame.path_to_player(path))
+    }
 
-                                display_radar_view(&horizontal, &vertical, &cells);
+    /// Enregistre la boussole reçue par un joueur, convertie en cap absolu.
+    ///
+    /// Retourne la nouvelle estimation de la sortie, dans le repère du joueur, lorsqu'elle
+    /// s'améliore.
+    pub fn record_compass(
+        &self,
+        player_name: &str,
+        player_state: &PlayerState,
+        angle: f32,
+    ) -> Option<GoalEstimate> {
+        let map = self.map.lock().ok()?;
+        let reading = CompassReading {
+            player_name: player_name.to_string(),
+            position: player_state.confirmed_position(),
+            bearing: absolute_bearing(player_state.confirmed_orientation(), angle),
+        };
+        match map.frame(player_name) {
+            Some(frame) => {
+                let estimate = self.triangulation.lock().ok()?.record(CompassReading {
+                    position: frame.to_map(reading.position),
+                    ..reading
+                })?;
+                Some(frame.estimate_to_player(estimate))
+            }
+            None => self
+                .local_triangulations
+                .lock()
+                .ok()?
+                .entry(player_name.to_string())
+                .or_default()
+                .record(reading),
+        }
+    }
 
-                                let direction = match config.navigation_mode.as_str() {
-                                    "random" => random_decide_move(&cells, player_name),
-                                    _ => tremaux_decide_move(&mut player_state, &cells, player_name),
-                                };
+    /// Position estimée de la sortie, dans le repère de `player_name`, d'après les boussoles
+    /// des joueurs dont il partage la carte.
+    pub fn estimated_goal(&self, player_name: &str) -> Option<GoalEstimate> {
+        let map = self.map.lock().ok()?;
+        match map.frame(player_name) {
+            Some(frame) => {
+                Some(frame.estimate_to_player(self.triangulation.lock().ok()?.estimate()?))
+            }
+            None => self
+                .local_triangulations
+                .lock()
+                .ok()?
+                .get(player_name)?
+                .estimate(),
+        }
+    }
 
+    /// Enregistre le secret reçu par un joueur et réveille les joueurs qui l'attendent.
+    pub fn record_secret(&self, player_name: &str, value: u64) -> Option<u64> {
+        let seq = self
+            .secrets
+            .lock()
+            .ok()?
+            .insert(player_name.to_string(), value);
+        self.secrets_updated.notify_all();
+        Some(seq)
+    }
 
-                                println!("[{}] Decided to move: {}", player_name, direction);
-                                move_player(&mut player_state, direction, &movement_logger);
+    /// Ajoute un joueur inscrit à ceux dont le secret est attendu.
+    pub fn register_player(&self, player_name: &str) {
+        if let Ok(mut secrets) = self.secrets.lock() {
+            secrets.subscribe(player_name);
+        }
+    }
 
-                                if let Err(e) = send_move_action(stream, direction, player_name) {
-                                    eprintln!("[{}] Failed to send move action: {}", player_name, e);
-                                }
-                            }
-                            Err(err) => {
-                                eprintln!("[{}] Failed to decode RadarView: {}", player_name, err);
-                            }
-                        }
+    /// Attend, au plus `policy.timeout()`, que chaque autre joueur inscrit ait un secret
+    /// frais reçu après `after_seq`, puis photographie les secrets disponibles.
+    ///
+    /// Le secret de `player_name` n'est pas attendu : son thread, bloqué ici, ne lit plus
+    /// les messages du serveur.
+    pub fn wait_for_secrets(
+        &self,
+        policy: &SecretWaitPolicy,
+        after_seq: u64,
+        player_name: &str,
+    ) -> Option<SecretSnapshot> {
+        let deadline = Instant::now() + policy.timeout();
+        let mut secrets = self.secrets.lock().ok()?;
+        loop {
+            let now = Instant::now();
+            if secrets
+                .missing(after_seq, policy.max_age(), now, player_name)
+                .is_empty()
+                || now >= deadline
+            {
+                return Some(secrets.snapshot(after_seq, policy.max_age(), now, player_name));
+            }
+            secrets = self
+                .secrets_updated
+                .wait_timeout(secrets, deadline - now)
+                .ok()?
+                .0;
+        }
+    }
+
+    /// Libère la frontière attribuée à un joueur qui arrête de jouer, ainsi que sa place
+    /// dans les secours en cours.
+    pub fn release_player(&self, player_name: &str) {
+        if let Ok(mut coordinator) = self.coordinator.lock() {
+            coordinator.release(player_name);
+        }
+        if let Ok(mut rescue) = self.rescue.lock() {
+            rescue.release(player_name);
+        }
+        if let Ok(mut secrets) = self.secrets.lock() {
+            secrets.unsubscribe(player_name);
+        }
+        self.secrets_updated.notify_all();
+    }
+
+    /// Mémorise la position d'un joueur aligné sur la carte commune, pour choisir les
+    /// secouristes d'un appel SOS. Les verrous sont pris dans l'ordre carte puis secours.
+    pub fn update_player_position(&self, player_name: &str, position: Position) {
+        let Some(frame) = self.map.lock().ok().and_then(|map| map.frame(player_name)) else {
+            return;
+        };
+        if let Ok(mut rescue) = self.rescue.lock() {
+            rescue.update_position(player_name, frame.to_map(position));
+        }
+    }
+
+    /// Diffuse l'appel à l'aide de `player_name`, bloqué en `position` par un challenge SOS.
+    ///
+    /// Retourne les coéquipiers envoyés à son secours, choisis parmi les joueurs alignés sur
+    /// la carte commune : un joueur qui n'y est pas encore situé ne peut pas être rejoint.
+    pub fn raise_sos(&self, player_name: &str, position: Position) -> Vec<String> {
+        let Ok(map) = self.map.lock() else {
+            return Vec::new();
+        };
+        let Some(frame) = map.frame(player_name) else {
+            println!(
+                "[{}] 🆘 Position inconnue de l'équipe, aucun secouriste envoyé",
+                player_name
+            );
+            return Vec::new();
+        };
+        self.rescue
+            .lock()
+            .map(|mut rescue| rescue.raise(map.shared(), player_name, frame.to_map(position)))
+            .unwrap_or_default()
+    }
+
+    /// Envoie `player_name` au secours du coéquipier en détresse le plus proche, s'il est
+    /// aligné sur la carte commune. La position du coéquipier est dans le repère du joueur.
+    pub fn volunteer_rescue(&self, player_name: &str) -> Option<(String, Position)> {
+        let frame = self.map.lock().ok()?.frame(player_name)?;
+        let (distressed, position) = self.rescue.lock().ok()?.volunteer(player_name)?;
+        Some((distressed, frame.to_player(position)))
+    }
+
+    /// Clôt l'appel à l'aide de `player_name`. Retourne `true` s'il était en cours.
+    pub fn resolve_sos(&self, player_name: &str) -> bool {
+        let Some(distress) = self
+            .rescue
+            .lock()
+            .ok()
+            .and_then(|mut rescue| rescue.resolve(player_name))
+        else {
+            return false;
+        };
+        println!(
+            "🆘 Appel de {} en {:?} résolu, secouristes libérés : {:?}",
+            player_name, distress.position, distress.rescuers
+        );
+        true
+    }
+
+    /// Coéquipier que `player_name` doit secourir, et plus court chemin connu jusqu'à lui
+    /// sans passer par `blocked` (sa propre cellule exceptée), dans le repère du joueur.
+    pub fn rescue_path(
+        &self,
+        player_name: &str,
+        position: Position,
+        blocked: &HashSet<Position>,
+    ) -> Option<(String, Vec<Position>)> {
+        let maps = self.map.lock().ok()?;
+        let frame = maps.frame(player_name)?;
+        let rescue = self.rescue.lock().ok()?;
+        let (distressed, target) = rescue.target(player_name)?;
+        let mut blocked = frame.cells_to_map(blocked);
+        blocked.remove(&target);
+        let path = bfs_avoiding(maps.shared(), frame.to_map(position), &blocked, |cell| {
+            cell == target
+        })?;
+        Some((distressed.to_string(), frame.path_to_player(path)))
+    }
+
+    /// Compte un challenge reçu sans solveur. Retourne le nombre de challenges de ce type
+    /// restés sans réponse.
+    pub fn record_unsupported_challenge(&self, kind: &str) -> u32 {
+        let Ok(mut counts) = self.unsupported_challenges.lock() else {
+            return 0;
+        };
+        let count = counts.entry(kind.to_string()).or_insert(0);
+        *count += 1;
+        *count
+    }
+
+    /// État sauvegardé d'un joueur, s'il existe.
+    pub fn saved_player(&self, player_name: &str) -> Option<SavedState> {
+        self.saved_players.lock().ok()?.get(player_name).cloned()
+    }
+
+    /// Met à jour l'état sauvegardé d'un joueur puis écrit la sauvegarde complète de l'équipe.
+    pub fn save_checkpoint(
+        &self,
+        player_name: &str,
+        player_state: &PlayerState,
+        path: &Path,
+    ) -> Result<(), String> {
+        // Le verrou est conservé pendant l'écriture pour que deux joueurs n'écrivent pas en même temps
+        let mut players = self
+            .saved_players
+            .lock()
+            .map_err(|e| format!("Verrouillage des sauvegardes impossible : {}", e))?;
+        players.insert(player_name.to_string(), player_state.snapshot());
+
+        let secrets = self
+            .secrets
+            .lock()
+            .map_err(|e| format!("Verrouillage des secrets impossible : {}", e))?
+            .values();
+
+        Checkpoint {
+            players: players.clone(),
+            secrets,
+        }
+        .save(path)
+    }
+}
+
+/// Raison pour laquelle la boucle de jeu d'un joueur s'est arrêtée.
+#[derive(Debug)]
+pub enum GameLoopExit {
+    /// La connexion a été perdue ou est devenue inutilisable.
+    Disconnected {
+        error: FrameError,
+        /// Nombre de déplacements confirmés par le serveur pendant la session
+        confirmed_moves: u32,
+    },
+    /// La stratégie n'a plus aucun déplacement à proposer.
+    Finished,
+}
+
+/// Boucle de jeu d'un joueur sur une connexion déjà inscrite.
+///
+/// L'état du joueur est fourni par l'appelant afin de pouvoir reprendre l'exploration
+/// sur une nouvelle connexion après une déconnexion.
+pub fn start_game_loop(
+    connection: &mut FramedStream,
+    player_name: &str,
+    game_state: &Arc<GameState>,
+    config: &Config,
+    player_state: &mut PlayerState,
+    movement_logger: &MovementLog,
+    strategy: &mut dyn NavigationStrategy,
+) -> GameLoopExit {
+    // Dernière vue radar décodée, pour rejouer un tour après un déplacement refusé ;
+    // la seconde copie reste telle que reçue, sans les cellules masquées depuis
+    let mut last_radar: Option<(RadarView, RadarView)> = None;
+    // Dernier tour resté sans déplacement, à rejouer après le prochain message
+    let mut stalled = false;
+    let mut moves_since_checkpoint = 0;
+    let mut confirmed_moves = 0;
+    let mut solvers = ChallengeSolvers::default();
+    loop {
+        println!("[{}] Waiting for message...", player_name);
+
+        let frame = match connection.reader.read_frame() {
+            Ok(frame) => frame,
+            Err(e) => {
+                eprintln!("[{}] Failed to read message: {}", player_name, e);
+                return GameLoopExit::Disconnected {
+                    error: e,
+                    confirmed_moves,
+                };
+            }
+        };
+
+        let mut replay = stalled;
+        match parse_server_message(frame) {
+            Ok(ServerMessage::RadarView(encoded_view)) => {
+                // Une nouvelle vue radar signifie que le déplacement précédent a été accepté
+                if let Some(confirmed) = player_state.confirm_move(movement_logger) {
+                    println!(
+                        "[{}] Déplacement confirmé : {}",
+                        player_name, confirmed.movement
+                    );
+                    // Un déplacement accepté signifie qu'aucun challenge ne bloque plus le joueur
+                    solvers.finish(&mut ChallengeContext {
+                        player_name,
+                        player_state,
+                        game_state,
+                        config,
+                        writer: &mut connection.writer,
+                    });
+                    strategy.observe_action_result(ActionResult::Confirmed(confirmed.movement));
+
+                    confirmed_moves += 1;
+                    moves_since_checkpoint += 1;
+                    if config.save_progress && moves_since_checkpoint >= config.checkpoint_interval
+                    {
+                        save_progress(game_state, config, player_name, player_state);
+                        moves_since_checkpoint = 0;
                     }
                 }
+                println!("RadarView: {}", encoded_view);
+                match RadarView::decode(&encoded_view) {
+                    Ok(radar) => {
+                        println!("[{}] Decoded RadarView: {:?}", player_name, radar);
+                        display_radar_view(&radar);
 
-                if let Some(hint) = msg.get("Hint") {
-                    if let Some(secret) = hint.get("Secret") {
-                        if let Some(secret_value) = secret.as_u64() {
-                            println!("[{}] Received secret: {}", player_name, secret_value);
-                            if let Ok(mut secrets) = game_state.secrets.lock() {
-                                secrets.insert(player_name.to_string(), secret_value);
-                            } else {
-                                eprintln!("[{}] Failed to lock secrets mutex", player_name);
-                            }
+                        if let Some(goal) =
+                            game_state.integrate_radar(player_name, &radar, player_state)
+                        {
+                            println!("[{}] 🚪 Sortie repérée en {:?}", player_name, goal);
+                        }
+                        game_state.update_player_position(player_name, player_state.position);
+                        strategy.observe_radar(&radar, player_state);
+
+                        replay = false;
+                        match play_turn(
+                            connection,
+                            player_state,
+                            &radar,
+                            &radar,
+                            strategy,
+                            game_state,
+                            player_name,
+                        ) {
+                            Turn::Finished => return GameLoopExit::Finished,
+                            turn => stalled = turn == Turn::Stalled,
                         }
+                        last_radar = Some((radar.clone(), radar));
+                    }
+                    Err(err) => {
+                        eprintln!("[{}] Failed to decode RadarView: {}", player_name, err);
                     }
                 }
-
-                if let Some(challenge) = msg.get("Challenge") {
-                    if let Some(modulo) = challenge.get("SecretSumModulo") {
-                        if let Some(modulo_value) = modulo.as_u64() {
+            }
+            Ok(ServerMessage::Hint(hint)) => {
+                strategy.observe_hint(&hint, player_state);
+                match hint {
+                    Hint::Secret(secret_value) => {
+                        match game_state.record_secret(player_name, secret_value) {
+                            Some(seq) => println!(
+                                "[{}] Received secret: {} (n° {})",
+                                player_name, secret_value, seq
+                            ),
+                            None => eprintln!("[{}] Failed to lock secrets mutex", player_name),
+                        }
+                    }
+                    Hint::RelativeCompass { angle } => {
+                        let bearing = absolute_bearing(player_state.confirmed_orientation(), angle);
+                        println!(
+                            "[{}] 🧭 Boussole : {}° relatif, cap absolu {}° depuis {:?}",
+                            player_name,
+                            angle,
+                            bearing,
+                            player_state.confirmed_position()
+                        );
+                        if let Some(estimate) =
+                            game_state.record_compass(player_name, player_state, angle)
+                        {
                             println!(
-                                "[{}] SecretSumModulo challenge received with modulo {}",
-                                player_name, modulo_value
+                                "[{}] 🎯 Sortie estimée vers {:?} ({:.1}, {:.1}), écart moyen {:.2} sur {} relevés",
+                                player_name,
+                                estimate.cell(),
+                                estimate.x,
+                                estimate.y,
+                                estimate.residual,
+                                estimate.readings
                             );
+                        }
+                    }
+                    Hint::GridSize { columns, rows } => {
+                        if game_state.set_grid_size(GridSize { columns, rows }) {
+                            println!(
+                                "[{}] 📐 Labyrinthe de {} colonnes sur {} lignes",
+                                player_name, columns, rows
+                            );
+                        }
+                    }
+                    Hint::SOSHelper => match game_state.volunteer_rescue(player_name) {
+                        Some((distressed, position)) => {
+                            println!(
+                                "[{}] 🆘 En renfort pour {} en {:?}",
+                                player_name, distressed, position
+                            );
+                        }
+                        None => println!(
+                            "[{}] 🆘 Indice SOSHelper reçu sans appel en cours",
+                            player_name
+                        ),
+                    },
+                }
+            }
+            Ok(ServerMessage::Challenge(challenge)) => {
+                let mut context = ChallengeContext {
+                    player_name,
+                    player_state,
+                    game_state,
+                    config,
+                    writer: &mut connection.writer,
+                };
+                if !solvers.solve(&challenge, &mut context) {
+                    let count = game_state.record_unsupported_challenge(challenge.kind());
+                    eprintln!(
+                        "[{}] ⚠️ Challenge non supporté : {} ({} reçu(s) sans solveur, solveurs disponibles : {})",
+                        player_name,
+                        challenge.kind(),
+                        count,
+                        solvers.kinds().collect::<Vec<_>>().join(", ")
+                    );
+                }
+            }
+            Ok(ServerMessage::ActionError(error)) => {
+                eprintln!(
+                    "[{}] Action refusée par le serveur : {}",
+                    player_name, error
+                );
+                player_state.last_action_error = Some(error);
+
+                if error == ActionError::InvalidChallengeSolution {
+                    solvers.retry(&mut ChallengeContext {
+                        player_name,
+                        player_state,
+                        game_state,
+                        config,
+                        writer: &mut connection.writer,
+                    });
+                }
+
+                let rejected = if error.rejects_move() {
+                    player_state.rollback_move()
+                } else {
+                    None
+                };
+                strategy.observe_action_result(ActionResult::Rejected {
+                    movement: rejected.map(|pending| pending.movement),
+                    error,
+                });
 
-                            handle_secret_sum_modulo(stream, player_name, &game_state, modulo_value);
+                if let Some(rejected) = rejected {
+                    println!(
+                        "[{}] Déplacement {} annulé, retour en {:?} orienté {:?}",
+                        player_name,
+                        rejected.movement,
+                        player_state.position,
+                        player_state.orientation
+                    );
+
+                    // Tant qu'un challenge bloque les déplacements, inutile de réessayer
+                    if error != ActionError::SolveChallengeFirst {
+                        if let Some((radar, received)) = last_radar.as_mut() {
+                            radar.mask(rejected.movement);
+                            replay = false;
+                            match play_turn(
+                                connection,
+                                player_state,
+                                radar,
+                                received,
+                                strategy,
+                                game_state,
+                                player_name,
+                            ) {
+                                Turn::Finished => return GameLoopExit::Finished,
+                                turn => stalled = turn == Turn::Stalled,
+                            }
                         }
                     }
                 }
             }
-            Err(e) => {
+            Ok(other) => {
                 eprintln!(
-                    "[{}] Failed to deserialize message: {:?}, error: {}",
-                    player_name, response_buffer, e
+                    "[{}] Message inattendu pendant la partie : {:?}",
+                    player_name, other
                 );
             }
+            Err(e) => {
+                eprintln!("[{}] {}", player_name, e);
+            }
+        }
+
+        // Le dernier tour n'a envoyé aucun déplacement : la situation a pu changer depuis
+        if replay {
+            if let Some((radar, received)) = last_radar.as_ref() {
+                match play_turn(
+                    connection,
+                    player_state,
+                    radar,
+                    received,
+                    strategy,
+                    game_state,
+                    player_name,
+                ) {
+                    Turn::Finished => return GameLoopExit::Finished,
+                    turn => stalled = turn == Turn::Stalled,
+                }
+            }
+        }
+    }
+}
+
+/// Sauvegarde la progression de l'équipe dans `config.checkpoint_path`.
+pub fn save_progress(
+    game_state: &GameState,
+    config: &Config,
+    player_name: &str,
+    player_state: &PlayerState,
+) {
+    let path = Path::new(&config.checkpoint_path);
+    match game_state.save_checkpoint(player_name, player_state, path) {
+        Ok(()) => println!(
+            "[{}] Progression sauvegardée dans {}",
+            player_name,
+            path.display()
+        ),
+        Err(e) => eprintln!("[{}] Échec de la sauvegarde : {}", player_name, e),
+    }
+}
+
+/// Délai entre deux décisions quand aucun déplacement n'est praticable.
+const WAIT_DELAY: Duration = Duration::from_millis(250);
+
+/// Nombre maximal de nouvelles décisions quand aucun déplacement n'est praticable, avant de
+/// reprendre la lecture des messages du serveur.
+const MAX_WAIT_RETRIES: u32 = 8;
+
+/// Issue d'un tour de jeu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Turn {
+    /// Un déplacement a été envoyé au serveur
+    Moved,
+    /// Aucun déplacement praticable malgré l'attente : le tour est rejoué au prochain message
+    Stalled,
+    /// La stratégie n'a plus aucun déplacement à proposer
+    Finished,
+}
+
+/// Demande un déplacement à la stratégie, l'applique localement puis l'envoie au serveur.
+///
+/// Si aucun déplacement n'est praticable, le joueur attend puis redemande, au plus
+/// `MAX_WAIT_RETRIES` fois, à partir de la vue `received` telle que reçue et libérée des
+/// alliés, qui ont pu se déplacer.
+fn play_turn(
+    connection: &mut FramedStream,
+    player_state: &mut PlayerState,
+    radar: &RadarView,
+    received: &RadarView,
+    strategy: &mut dyn NavigationStrategy,
+    game_state: &GameState,
+    player_name: &str,
+) -> Turn {
+    let mut context = NavigationContext {
+        player_name,
+        player_state,
+        game_state,
+    };
+    let mut decision = strategy.decide(radar, &mut context);
+    if decision.is_none() && !strategy.finished() {
+        println!("[{}] Aucun déplacement praticable, attente", player_name);
+        let vacated = received.without_allies();
+        let mut retries = 0;
+        while decision.is_none() && !strategy.finished() && retries < MAX_WAIT_RETRIES {
+            thread::sleep(WAIT_DELAY);
+            decision = strategy.decide(&vacated, &mut context);
+            retries += 1;
+        }
+    }
+    let Some(direction) = decision else {
+        if !strategy.finished() {
+            println!(
+                "[{}] Toujours bloqué, reprise de la lecture des messages",
+                player_name
+            );
+            return Turn::Stalled;
         }
+        if game_state.exploration_exhausted(player_name) {
+            println!(
+                "[{}] ⚠️ Labyrinthe entièrement exploré, aucune sortie trouvée",
+                player_name
+            );
+        }
+        return Turn::Finished;
+    };
+
+    println!("[{}] Decided to move: {}", player_name, direction);
+    move_player(player_state, direction);
+
+    if let Err(e) = send_move_action(&mut connection.writer, direction, player_name) {
+        eprintln!("[{}] Failed to send move action: {}", player_name, e);
+        player_state.rollback_move();
     }
+    Turn::Moved
 }
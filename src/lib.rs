@@ -1,5 +1,23 @@
+pub mod challenge;
+pub mod checkpoint;
 pub mod client;
+pub mod codec;
+pub mod config;
+pub mod coordinator;
+pub mod direction;
 pub mod game;
+pub mod map;
+pub mod navigation;
+pub mod planner;
 pub mod player;
+pub mod protocol;
+pub mod radar;
+pub mod rescue;
+pub mod secrets;
+pub mod team_map;
+pub mod triangulation;
 pub mod utils;
-pub mod config;
+
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod tests;
@@ -0,0 +1,236 @@
+use crate::direction::CardinalDirection;
+use crate::player::{Orientation, Position};
+use crate::radar::{Cell, CellNature, Passage, RadarView};
+use std::collections::HashMap;
+
+/// Passage entre deux cellules voisines, en coordonnées absolues.
+///
+/// Un même passage peut être désigné depuis ses deux cellules ; il est normalisé sur le côté
+/// nord ou ouest pour servir de clé unique.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Edge {
+    cell: Position,
+    side: CardinalDirection,
+}
+
+impl Edge {
+    pub fn new(cell: Position, side: CardinalDirection) -> Self {
+        match side {
+            CardinalDirection::East => Self {
+                cell: cell.neighbour(CardinalDirection::East),
+                side: CardinalDirection::West,
+            },
+            CardinalDirection::South => Self {
+                cell: cell.neighbour(CardinalDirection::South),
+                side: CardinalDirection::North,
+            },
+            CardinalDirection::North | CardinalDirection::West => Self { cell, side },
+        }
+    }
+
+    /// Les deux cellules reliées par ce passage.
+    pub fn cells(&self) -> (Position, Position) {
+        (self.cell, self.cell.neighbour(self.side))
+    }
+
+    /// Le même passage dans un repère décalé de `offset`.
+    pub fn translated(self, offset: Position) -> Self {
+        Self {
+            cell: self.cell + offset,
+            side: self.side,
+        }
+    }
+}
+
+/// Dimensions du labyrinthe, transmises par l'indice `GridSize`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridSize {
+    pub columns: u32,
+    pub rows: u32,
+}
+
+/// Carte du labyrinthe construite à partir des vues radar.
+///
+/// Toutes les positions sont exprimées dans un même repère : celui d'un joueur, qui y démarre
+/// en `(0, 0)` orienté au nord, ou celui de la grille réelle pour la carte de l'équipe
+/// (voir `TeamMap`).
+#[derive(Debug, Clone, Default)]
+pub struct MazeMap {
+    cells: HashMap<Position, Cell>,
+    passages: HashMap<Edge, Passage>,
+    goal: Option<Position>,
+    grid_size: Option<GridSize>,
+    /// Coins (min, max) du rectangle englobant les cellules connues
+    extent: Option<(Position, Position)>,
+}
+
+impl MazeMap {
+    /// Intègre une vue radar reçue par un joueur situé en `position` et orienté selon `orientation`.
+    ///
+    /// Les informations inconnues (`Undefined`) du radar n'effacent jamais ce qui est déjà connu.
+    pub fn integrate(&mut self, radar: &RadarView, position: Position, orientation: Orientation) {
+        let view = radar.to_world(position, orientation);
+
+        for (absolute, cell) in view.cells {
+            if cell == Cell::Undefined {
+                continue;
+            }
+            if cell.is_goal() {
+                self.goal = Some(absolute);
+            }
+            self.cells.insert(absolute, cell);
+            self.extend(absolute);
+        }
+        for (edge, passage) in view.passages {
+            self.record_passage(edge, passage);
+        }
+    }
+
+    fn extend(&mut self, position: Position) {
+        let (min, max) = self.extent.get_or_insert((position, position));
+        *min = Position::new(min.x().min(position.x()), min.y().min(position.y()));
+        *max = Position::new(max.x().max(position.x()), max.y().max(position.y()));
+    }
+
+    /// Enregistre les dimensions du labyrinthe. Retourne `true` si elles étaient inconnues
+    /// ou différentes.
+    pub fn set_grid_size(&mut self, grid_size: GridSize) -> bool {
+        self.grid_size.replace(grid_size) != Some(grid_size)
+    }
+
+    pub fn grid_size(&self) -> Option<GridSize> {
+        self.grid_size
+    }
+
+    /// Indique si `position` peut appartenir au labyrinthe, compte tenu de ses dimensions
+    /// et des cellules déjà vues. Sans dimensions connues, toute position est possible.
+    pub fn may_contain(&self, position: Position) -> bool {
+        let (Some(size), Some((min, max))) = (self.grid_size, self.extent) else {
+            return true;
+        };
+        let (columns, rows) = (size.columns as i32, size.rows as i32);
+        (max.x() - columns < position.x() && position.x() < min.x() + columns)
+            && (max.y() - rows < position.y() && position.y() < min.y() + rows)
+    }
+
+    /// Position, dans le repère de la carte, de la cellule `(0, 0)` de la grille réelle.
+    ///
+    /// Elle n'est connue qu'une fois que les cellules vues couvrent toute la largeur et
+    /// toute la hauteur du labyrinthe.
+    pub fn grid_origin(&self) -> Option<Position> {
+        let (size, (min, max)) = (self.grid_size?, self.extent?);
+        (max.x() - min.x() + 1 == size.columns as i32 && max.y() - min.y() + 1 == size.rows as i32)
+            .then_some(min)
+    }
+
+    /// Coordonnées de `position` dans la grille réelle, une fois son origine connue.
+    pub fn to_grid(&self, position: Position) -> Option<Position> {
+        let origin = self.grid_origin()?;
+        Some(Position::new(
+            position.x() - origin.x(),
+            position.y() - origin.y(),
+        ))
+    }
+
+    /// Ajoute à cette carte tout ce que connaît `other`, dont les positions sont décalées
+    /// de `offset` dans ce repère. Ce que `other` a vu prime sur la carte existante.
+    pub fn merge(&mut self, other: &MazeMap, offset: Position) {
+        for (&position, &cell) in &other.cells {
+            self.cells.insert(position + offset, cell);
+            self.extend(position + offset);
+        }
+        for (&edge, &passage) in &other.passages {
+            self.record_passage(edge.translated(offset), passage);
+        }
+        if let Some(goal) = other.goal {
+            self.goal = Some(goal + offset);
+        }
+    }
+
+    fn record_passage(&mut self, edge: Edge, passage: Passage) {
+        if passage != Passage::Undefined {
+            self.passages.insert(edge, passage);
+        }
+    }
+
+    /// Dernier contenu observé d'une cellule, s'il est connu.
+    pub fn cell(&self, position: Position) -> Option<Cell> {
+        self.cells.get(&position).copied()
+    }
+
+    /// Passage observé entre `position` et sa voisine dans la direction `side`.
+    pub fn passage(&self, position: Position, side: CardinalDirection) -> Passage {
+        self.passages
+            .get(&Edge::new(position, side))
+            .copied()
+            .unwrap_or(Passage::Undefined)
+    }
+
+    pub fn is_open(&self, position: Position, side: CardinalDirection) -> bool {
+        self.passage(position, side) == Passage::Open
+    }
+
+    /// Voisines de `position` atteignables par un passage ouvert, avec la direction à prendre.
+    pub fn open_neighbours(
+        &self,
+        position: Position,
+    ) -> impl Iterator<Item = (CardinalDirection, Position)> + '_ {
+        CardinalDirection::ALL
+            .into_iter()
+            .filter(move |&side| self.is_open(position, side))
+            .map(move |side| (side, position + side))
+    }
+
+    /// Une cellule frontière est connue, et un passage ouvert la relie à une cellule
+    /// encore jamais vue qui peut appartenir au labyrinthe.
+    pub fn is_frontier(&self, position: Position) -> bool {
+        self.cells.contains_key(&position)
+            && self
+                .open_neighbours(position)
+                .any(|(_, next)| !self.cells.contains_key(&next) && self.may_contain(next))
+    }
+
+    /// Cellules frontières de la carte.
+    pub fn frontiers(&self) -> impl Iterator<Item = Position> + '_ {
+        self.cells
+            .keys()
+            .copied()
+            .filter(|&position| self.is_frontier(position))
+    }
+
+    /// Position de la sortie, si un joueur l'a déjà vue.
+    pub fn goal(&self) -> Option<Position> {
+        self.goal
+    }
+
+    /// Cellules contenant un indice.
+    pub fn hints(&self) -> impl Iterator<Item = Position> + '_ {
+        self.cells.iter().filter_map(|(position, cell)| match cell {
+            Cell::Known {
+                nature: CellNature::Hint,
+                ..
+            } => Some(*position),
+            _ => None,
+        })
+    }
+
+    /// Cellules connues et leur contenu.
+    pub fn cells(&self) -> impl Iterator<Item = (Position, Cell)> + '_ {
+        self.cells.iter().map(|(position, cell)| (*position, *cell))
+    }
+
+    pub fn known_cells(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Le labyrinthe a été exploré sans que personne n'en voie la sortie : toutes les cellules
+    /// de la grille sont connues, ou plus aucune frontière ne mène à l'inconnu.
+    pub fn is_exhausted(&self) -> bool {
+        let all_seen = self
+            .grid_size
+            .is_some_and(|size| self.cells.len() as u64 >= size.columns as u64 * size.rows as u64);
+        self.goal.is_none()
+            && !self.cells.is_empty()
+            && (all_seen || self.frontiers().next().is_none())
+    }
+}
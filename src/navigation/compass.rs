@@ -0,0 +1,108 @@
+use super::{
+    blocked_neighbours, frontiers_blocked, reachable_goal, NavigationContext, NavigationStrategy,
+};
+use crate::direction::{absolute_bearing, bearing_vector, RelativeDirection};
+use crate::planner::{to_relative_moves, SearchTree};
+use crate::player::{PlayerState, Position};
+use crate::protocol::Hint;
+use crate::radar::RadarView;
+use crate::triangulation::GoalEstimate;
+
+/// Poids de l'avancée dans la direction de la boussole face à la longueur du chemin :
+/// une frontière qui avance d'une case vers la sortie compte comme un chemin plus court
+/// de `COMPASS_BIAS` case.
+const COMPASS_BIAS: f64 = 0.8;
+
+/// Exploration par frontières orientée par les indices `RelativeCompass`.
+///
+/// Parmi les frontières atteignables de la carte du joueur, le joueur choisit celle qui
+/// minimise la longueur du chemin moins son avancée vers la sortie : vers la position
+/// triangulée par l'équipe si elle est connue, sinon dans la direction de la dernière
+/// boussole reçue. Sans boussole, c'est la frontière la plus proche.
+///
+/// Comme pour `Frontier`, des frontières momentanément bloquées ne l'arrêtent pas : le
+/// joueur s'écarte ou attend.
+#[derive(Debug, Clone, Default)]
+pub struct Compass {
+    /// Dernière boussole : position du joueur et vecteur unitaire du cap absolu
+    heading: Option<(Position, (f64, f64))>,
+    /// Plus aucune frontière atteignable, même en ignorant les cellules bloquées
+    finished: bool,
+}
+
+impl Compass {
+    /// Score d'une frontière (plus petit = meilleur), à une distance `distance` du joueur.
+    fn score(&self, frontier: Position, distance: u32, estimate: Option<GoalEstimate>) -> f64 {
+        let progress = match (estimate, self.heading) {
+            // Le terme constant (distance du joueur à l'estimation) est omis
+            (Some(estimate), _) => {
+                -(frontier.x() as f64 - estimate.x).hypot(frontier.y() as f64 - estimate.y)
+            }
+            (None, Some((origin, (dx, dy)))) => {
+                (frontier.x() - origin.x()) as f64 * dx + (frontier.y() - origin.y()) as f64 * dy
+            }
+            (None, None) => 0.0,
+        };
+        distance as f64 - COMPASS_BIAS * progress
+    }
+}
+
+impl NavigationStrategy for Compass {
+    fn observe_hint(&mut self, hint: &Hint, player_state: &PlayerState) {
+        if let Hint::RelativeCompass { angle } = hint {
+            let bearing = absolute_bearing(player_state.confirmed_orientation(), *angle);
+            self.heading = Some((player_state.confirmed_position(), bearing_vector(bearing)));
+        }
+    }
+
+    fn decide(
+        &mut self,
+        radar: &RadarView,
+        context: &mut NavigationContext,
+    ) -> Option<RelativeDirection> {
+        if let Some(direction) = reachable_goal(radar, context.player_name) {
+            return Some(direction);
+        }
+
+        let position = context.player_state.position;
+        let blocked = blocked_neighbours(radar, context.player_state);
+        let estimate = context.game_state.estimated_goal(context.player_name);
+        let path = {
+            let maps = context.game_state.map.lock().ok()?;
+            let (map, frame) = maps.view(context.player_name)?;
+            let start = frame.to_map(position);
+            let tree = SearchTree::new(map, start, &frame.cells_to_map(&blocked));
+            let target = tree
+                .distances()
+                .filter(|&(cell, _)| cell != start && map.is_frontier(cell))
+                .min_by(|&(a, da), &(b, db)| {
+                    self.score(frame.to_player(a), da, estimate)
+                        .total_cmp(&self.score(frame.to_player(b), db, estimate))
+                        .then((a.x(), a.y()).cmp(&(b.x(), b.y())))
+                });
+            match target {
+                Some((target, _)) => Some(frame.path_to_player(tree.path_to(target)?)),
+                None => None,
+            }
+        };
+        let Some(path) = path else {
+            return frontiers_blocked(radar, context, &mut self.finished);
+        };
+
+        println!(
+            "[DEBUG {}] Frontière visée (boussole {:?}, estimation {:?}) : {:?}, {} pas",
+            context.player_name,
+            self.heading,
+            estimate.map(|estimate| estimate.cell()),
+            path.last(),
+            path.len() - 1
+        );
+        let direction = to_relative_moves(&path[..2], context.player_state.orientation)?[0];
+        context.player_state.last_direction = Some(direction);
+        Some(direction)
+    }
+
+    fn finished(&self) -> bool {
+        self.finished
+    }
+}
@@ -0,0 +1,83 @@
+use super::{blocked_neighbours, ActionResult, NavigationContext, NavigationStrategy};
+use crate::direction::RelativeDirection;
+use crate::planner::to_relative_moves;
+use crate::player::PlayerState;
+use crate::protocol::Hint;
+use crate::radar::RadarView;
+
+/// Enveloppe une stratégie d'exploration : dès que la sortie figure sur la carte du joueur
+/// (celle de l'équipe, une fois alignée), il suit le plus court chemin connu pour la rejoindre.
+///
+/// Tant que la sortie est inconnue, ou qu'aucun chemin connu n'y mène, la stratégie
+/// enveloppée décide. Elle continue de recevoir toutes les observations.
+pub struct ExitRouting {
+    inner: Box<dyn NavigationStrategy>,
+    /// Le joueur est en route vers la sortie
+    routing: bool,
+}
+
+impl ExitRouting {
+    pub fn new(inner: Box<dyn NavigationStrategy>) -> Self {
+        Self {
+            inner,
+            routing: false,
+        }
+    }
+}
+
+impl NavigationStrategy for ExitRouting {
+    fn observe_radar(&mut self, radar: &RadarView, player_state: &PlayerState) {
+        self.inner.observe_radar(radar, player_state);
+    }
+
+    fn observe_hint(&mut self, hint: &Hint, player_state: &PlayerState) {
+        self.inner.observe_hint(hint, player_state);
+    }
+
+    fn observe_action_result(&mut self, result: ActionResult) {
+        self.inner.observe_action_result(result);
+    }
+
+    fn finished(&self) -> bool {
+        self.inner.finished()
+    }
+
+    fn decide(
+        &mut self,
+        radar: &RadarView,
+        context: &mut NavigationContext,
+    ) -> Option<RelativeDirection> {
+        let blocked = blocked_neighbours(radar, context.player_state);
+        let path = context
+            .game_state
+            .exit_path(context.player_name, context.player_state.position, &blocked)
+            .filter(|path| path.len() >= 2);
+
+        match path {
+            Some(path) => {
+                if !self.routing {
+                    println!(
+                        "[{}] 🚪 Passage en mode « aller à la sortie » : {:?}, {} pas",
+                        context.player_name,
+                        path.last(),
+                        path.len() - 1
+                    );
+                    self.routing = true;
+                }
+                let direction = to_relative_moves(&path[..2], context.player_state.orientation)?[0];
+                context.player_state.last_direction = Some(direction);
+                Some(direction)
+            }
+            None => {
+                if self.routing {
+                    println!(
+                        "[{}] Chemin vers la sortie perdu, reprise de l'exploration",
+                        context.player_name
+                    );
+                    self.routing = false;
+                }
+                self.inner.decide(radar, context)
+            }
+        }
+    }
+}
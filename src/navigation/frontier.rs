@@ -0,0 +1,50 @@
+use super::{
+    blocked_neighbours, frontiers_blocked, reachable_goal, NavigationContext, NavigationStrategy,
+};
+use crate::direction::RelativeDirection;
+use crate::planner::to_relative_moves;
+use crate::radar::RadarView;
+
+/// Exploration par frontières : le joueur se rend à la cellule frontière (cellule connue
+/// reliée à une cellule jamais vue) que le coordinateur de l'équipe lui a attribuée, en
+/// général la plus proche qu'aucun coéquipier ne vise déjà. Tant que sa carte n'a pas
+/// rejoint celle de l'équipe, il se rend simplement à la frontière la plus proche.
+///
+/// Si les frontières restantes ne sont atteignables qu'en passant par une cellule bloquée
+/// (coéquipier, déplacement refusé), le joueur s'écarte ou attend ; la stratégie ne s'arrête
+/// que lorsqu'aucune frontière de sa carte n'est plus atteignable.
+#[derive(Debug, Clone, Default)]
+pub struct Frontier {
+    finished: bool,
+}
+
+impl NavigationStrategy for Frontier {
+    fn decide(
+        &mut self,
+        radar: &RadarView,
+        context: &mut NavigationContext,
+    ) -> Option<RelativeDirection> {
+        if let Some(direction) = reachable_goal(radar, context.player_name) {
+            return Some(direction);
+        }
+
+        let position = context.player_state.position;
+        let orientation = context.player_state.orientation;
+        let blocked = blocked_neighbours(radar, context.player_state);
+
+        let path = context
+            .game_state
+            .frontier_path(context.player_name, position, &blocked);
+        let Some(path) = path else {
+            return frontiers_blocked(radar, context, &mut self.finished);
+        };
+
+        let direction = to_relative_moves(&path[..2], orientation)?[0];
+        context.player_state.last_direction = Some(direction);
+        Some(direction)
+    }
+
+    fn finished(&self) -> bool {
+        self.finished
+    }
+}
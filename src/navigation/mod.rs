@@ -0,0 +1,201 @@
+use crate::config::ConfigError;
+use crate::direction::RelativeDirection;
+use crate::game::GameState;
+use crate::player::{PlayerState, Position};
+use crate::protocol::{ActionError, Hint};
+use crate::radar::RadarView;
+use std::collections::{BTreeMap, HashSet};
+
+pub mod compass;
+pub mod exit;
+pub mod frontier;
+pub mod random;
+pub mod rescue;
+pub mod tremaux;
+pub mod wall_follower;
+
+pub use compass::Compass;
+pub use exit::ExitRouting;
+pub use frontier::Frontier;
+pub use random::RandomWalk;
+pub use rescue::RescueRouting;
+pub use tremaux::Tremaux;
+pub use wall_follower::{Pledge, WallFollower};
+
+/// Ce que le joueur sait au moment de choisir son déplacement.
+pub struct NavigationContext<'a> {
+    pub player_name: &'a str,
+    pub player_state: &'a mut PlayerState,
+    pub game_state: &'a GameState,
+}
+
+/// Issue d'une action envoyée au serveur.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionResult {
+    /// Le serveur a accepté le déplacement (une nouvelle vue radar est arrivée).
+    Confirmed(RelativeDirection),
+    /// Le serveur a refusé l'action ; `movement` est le déplacement annulé, s'il y en avait un.
+    Rejected {
+        movement: Option<RelativeDirection>,
+        error: ActionError,
+    },
+}
+
+/// Stratégie de navigation d'un joueur dans le labyrinthe.
+///
+/// Une instance est créée par joueur et conservée d'une connexion à l'autre : elle peut
+/// donc garder sa propre mémoire de l'exploration. Les méthodes `observe_*` sont appelées
+/// par la boucle de jeu à chaque message du serveur, `decide` lorsqu'un déplacement est attendu.
+pub trait NavigationStrategy: Send {
+    fn observe_radar(&mut self, _radar: &RadarView, _player_state: &PlayerState) {}
+
+    fn observe_hint(&mut self, _hint: &Hint, _player_state: &PlayerState) {}
+
+    fn observe_action_result(&mut self, _result: ActionResult) {}
+
+    /// Choisit le prochain déplacement à partir de la vue radar courante.
+    ///
+    /// Seuls des déplacements praticables d'après `radar` sont proposés. `None` signifie
+    /// qu'aucun ne l'est pour l'instant : le joueur attend, sauf si `finished` le renvoie.
+    fn decide(
+        &mut self,
+        radar: &RadarView,
+        context: &mut NavigationContext,
+    ) -> Option<RelativeDirection>;
+
+    /// La stratégie n'a plus rien à explorer : le joueur s'arrête au lieu d'attendre.
+    fn finished(&self) -> bool {
+        false
+    }
+}
+
+/// Construit une nouvelle instance de stratégie.
+pub type StrategyFactory = fn() -> Box<dyn NavigationStrategy>;
+
+/// Associe les noms utilisables dans `navigation_mode` aux stratégies correspondantes.
+#[derive(Clone)]
+pub struct StrategyRegistry {
+    factories: BTreeMap<String, StrategyFactory>,
+}
+
+impl StrategyRegistry {
+    /// Registre sans aucune stratégie.
+    pub fn empty() -> Self {
+        Self {
+            factories: BTreeMap::new(),
+        }
+    }
+
+    /// Ajoute (ou remplace) la stratégie `name`.
+    pub fn register(&mut self, name: &str, factory: StrategyFactory) -> &mut Self {
+        self.factories.insert(name.to_string(), factory);
+        self
+    }
+
+    /// Noms des stratégies disponibles, par ordre alphabétique.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.factories.keys().map(String::as_str)
+    }
+
+    /// Crée une instance de la stratégie `name`.
+    pub fn create(&self, name: &str) -> Result<Box<dyn NavigationStrategy>, ConfigError> {
+        let factory =
+            self.factories
+                .get(name)
+                .ok_or_else(|| ConfigError::UnknownNavigationMode {
+                    mode: name.to_string(),
+                    available: self.names().map(str::to_string).collect(),
+                })?;
+        Ok(factory())
+    }
+}
+
+impl Default for StrategyRegistry {
+    /// Registre contenant les stratégies fournies avec le client.
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry
+            .register("tremaux", || Box::<Tremaux>::default())
+            .register("random", || Box::new(RandomWalk))
+            .register("frontier", || Box::<Frontier>::default())
+            .register("compass", || Box::<Compass>::default())
+            .register("left_hand", || Box::new(WallFollower::left_hand()))
+            .register("right_hand", || Box::new(WallFollower::right_hand()))
+            .register("pledge", || Box::<Pledge>::default());
+        registry
+    }
+}
+
+/// Ordre dans lequel les directions sont examinées par les stratégies.
+pub(crate) const MOVE_PRIORITY: [RelativeDirection; 4] = [
+    RelativeDirection::Front,
+    RelativeDirection::Back,
+    RelativeDirection::Left,
+    RelativeDirection::Right,
+];
+
+/// Direction d'une sortie atteignable directement depuis la cellule du joueur.
+pub(crate) fn reachable_goal(radar: &RadarView, player_name: &str) -> Option<RelativeDirection> {
+    let direction = MOVE_PRIORITY
+        .into_iter()
+        .find(|&direction| radar.neighbour(direction).is_goal() && radar.is_reachable(direction))?;
+    println!(
+        "[DEBUG {}] 🚪 Sortie détectée ! Se dirige vers: {}",
+        player_name, direction
+    );
+    Some(direction)
+}
+
+/// Directions dans lesquelles la cellule voisine est libre et accessible, dans l'ordre de `MOVE_PRIORITY`.
+pub(crate) fn legal_moves(radar: &RadarView) -> impl Iterator<Item = RelativeDirection> + '_ {
+    MOVE_PRIORITY
+        .into_iter()
+        .filter(|&direction| radar.neighbour(direction).is_free() && radar.is_reachable(direction))
+}
+
+/// Réaction d'une exploration par frontières quand aucune frontière n'est atteignable sans
+/// passer par une cellule bloquée.
+///
+/// Si l'une d'elles l'est en ignorant ces cellules (coéquipier, déplacement refusé), le joueur
+/// s'écarte, ou attend s'il ne peut pas bouger. Sinon l'exploration est terminée : `finished`
+/// passe à `true`.
+pub(crate) fn frontiers_blocked(
+    radar: &RadarView,
+    context: &mut NavigationContext,
+    finished: &mut bool,
+) -> Option<RelativeDirection> {
+    let position = context.player_state.position;
+    if !context
+        .game_state
+        .frontier_reachable(context.player_name, position)
+    {
+        println!(
+            "[{}] Plus aucune frontière atteignable",
+            context.player_name
+        );
+        *finished = true;
+        return None;
+    }
+    let direction = legal_moves(radar).next()?;
+    println!(
+        "[{}] Frontières momentanément inaccessibles, pas de côté : {}",
+        context.player_name, direction
+    );
+    context.player_state.last_direction = Some(direction);
+    Some(direction)
+}
+
+/// Cellules voisines que la vue radar interdit pour l'instant (mur, autre joueur,
+/// déplacement refusé), en coordonnées absolues.
+pub(crate) fn blocked_neighbours(
+    radar: &RadarView,
+    player_state: &PlayerState,
+) -> HashSet<Position> {
+    MOVE_PRIORITY
+        .into_iter()
+        .filter(|&direction| {
+            !(radar.neighbour(direction).is_free() && radar.is_reachable(direction))
+        })
+        .map(|direction| player_state.position + player_state.orientation.rotate(direction))
+        .collect()
+}
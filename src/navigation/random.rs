@@ -0,0 +1,32 @@
+use super::{legal_moves, reachable_goal, NavigationContext, NavigationStrategy};
+use crate::direction::RelativeDirection;
+use crate::radar::RadarView;
+use rand::rng;
+use rand::seq::IndexedRandom;
+
+/// Marche aléatoire.
+pub struct RandomWalk;
+
+impl NavigationStrategy for RandomWalk {
+    fn decide(
+        &mut self,
+        radar: &RadarView,
+        context: &mut NavigationContext,
+    ) -> Option<RelativeDirection> {
+        random_decide_move(radar, context.player_name)
+    }
+}
+
+/// Choisit au hasard parmi les cases libres accessibles, la sortie en priorité.
+///
+/// Retourne `None` si aucune case voisine n'est accessible.
+pub fn random_decide_move(radar: &RadarView, player_name: &str) -> Option<RelativeDirection> {
+    let mut rng = rng();
+
+    if let Some(direction) = reachable_goal(radar, player_name) {
+        return Some(direction);
+    }
+
+    let moves: Vec<RelativeDirection> = legal_moves(radar).collect();
+    moves.choose(&mut rng).copied()
+}
@@ -0,0 +1,71 @@
+use super::{blocked_neighbours, ActionResult, NavigationContext, NavigationStrategy};
+use crate::direction::RelativeDirection;
+use crate::planner::to_relative_moves;
+use crate::player::PlayerState;
+use crate::protocol::Hint;
+use crate::radar::RadarView;
+
+/// Enveloppe une stratégie : tant que le joueur est envoyé au secours d'un coéquipier
+/// (challenge SOS), il suit le plus court chemin connu jusqu'à lui.
+///
+/// Une fois le coéquipier rejoint, l'appel est clos et la stratégie enveloppée reprend.
+pub struct RescueRouting {
+    inner: Box<dyn NavigationStrategy>,
+}
+
+impl RescueRouting {
+    pub fn new(inner: Box<dyn NavigationStrategy>) -> Self {
+        Self { inner }
+    }
+}
+
+impl NavigationStrategy for RescueRouting {
+    fn observe_radar(&mut self, radar: &RadarView, player_state: &PlayerState) {
+        self.inner.observe_radar(radar, player_state);
+    }
+
+    fn observe_hint(&mut self, hint: &Hint, player_state: &PlayerState) {
+        self.inner.observe_hint(hint, player_state);
+    }
+
+    fn observe_action_result(&mut self, result: ActionResult) {
+        self.inner.observe_action_result(result);
+    }
+
+    fn finished(&self) -> bool {
+        self.inner.finished()
+    }
+
+    fn decide(
+        &mut self,
+        radar: &RadarView,
+        context: &mut NavigationContext,
+    ) -> Option<RelativeDirection> {
+        let blocked = blocked_neighbours(radar, context.player_state);
+        let rescue = context.game_state.rescue_path(
+            context.player_name,
+            context.player_state.position,
+            &blocked,
+        );
+
+        match rescue {
+            Some((distressed, path)) if path.len() >= 2 => {
+                println!(
+                    "[{}] 🆘 En route vers {} : {} pas",
+                    context.player_name,
+                    distressed,
+                    path.len() - 1
+                );
+                let direction = to_relative_moves(&path[..2], context.player_state.orientation)?[0];
+                context.player_state.last_direction = Some(direction);
+                Some(direction)
+            }
+            Some((distressed, _)) => {
+                println!("[{}] 🆘 {} a été rejoint", context.player_name, distressed);
+                context.game_state.resolve_sos(&distressed);
+                self.inner.decide(radar, context)
+            }
+            None => self.inner.decide(radar, context),
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use super::{legal_moves, reachable_goal, NavigationContext, NavigationStrategy, MOVE_PRIORITY};
+use crate::direction::{CardinalDirection, RelativeDirection};
+use crate::map::Edge;
+use crate::player::{PlayerState, Position};
+use crate::radar::{Passage, RadarView};
+use std::collections::HashMap;
+
+/// Algorithme de Trémaux : chaque passage emprunté reçoit une marque, et aucun passage
+/// n'est parcouru plus de deux fois.
+///
+/// Règles appliquées à chaque cellule atteinte :
+/// * arrivée par un passage neuf dans une cellule déjà connue : demi-tour ;
+/// * sinon, prendre un passage sans marque, à défaut un passage marqué une fois ;
+/// * un cul-de-sac ne laisse que le passage d'arrivée, marqué une fois : demi-tour.
+///
+/// Dans un labyrinthe parfait, l'exploration complète prend donc au plus deux fois
+/// le nombre de passages.
+#[derive(Debug, Clone, Default)]
+pub struct Tremaux {
+    /// Nombre de passages par arête (0, 1 ou 2)
+    marks: HashMap<Edge, u8>,
+    /// Cellule du joueur lors de la dernière vue radar
+    previous: Option<Position>,
+    /// Côté de la cellule courante par lequel le joueur est arrivé
+    entry: Option<CardinalDirection>,
+    /// Tous les passages praticables ont été parcourus deux fois
+    finished: bool,
+}
+
+impl Tremaux {
+    /// Nombre de marques du passage entre `position` et sa voisine dans `side`.
+    pub fn marks(&self, position: Position, side: CardinalDirection) -> u8 {
+        self.marks
+            .get(&Edge::new(position, side))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Choisit le passage à emprunter parmi `passages` (côté, marques), depuis `position`.
+    fn choose(
+        &self,
+        position: Position,
+        passages: &[(CardinalDirection, u8)],
+    ) -> Option<CardinalDirection> {
+        if let Some(entry) = self.entry {
+            let entry_open = passages.iter().any(|&(side, _)| side == entry);
+            let known_cell = passages
+                .iter()
+                .any(|&(side, marks)| side != entry && marks > 0);
+            if entry_open && known_cell && self.marks(position, entry) == 1 {
+                return Some(entry);
+            }
+        }
+
+        // Le moins de marques d'abord ; à égalité, éviter de revenir sur ses pas
+        passages
+            .iter()
+            .filter(|&&(_, marks)| marks < 2)
+            .min_by_key(|&&(side, marks)| (marks, Some(side) == self.entry))
+            .map(|&(side, _)| side)
+    }
+}
+
+impl NavigationStrategy for Tremaux {
+    fn observe_radar(&mut self, _radar: &RadarView, player_state: &PlayerState) {
+        let position = player_state.position;
+        if let Some(previous) = self.previous.filter(|&previous| previous != position) {
+            // Le joueur vient de traverser le passage entre sa cellule précédente et la courante
+            self.entry = CardinalDirection::ALL
+                .into_iter()
+                .find(|&side| previous + side == position)
+                .map(|side| {
+                    let marks = self.marks.entry(Edge::new(previous, side)).or_insert(0);
+                    *marks = marks.saturating_add(1).min(2);
+                    side.opposite()
+                });
+        }
+        self.previous = Some(position);
+    }
+
+    fn decide(
+        &mut self,
+        radar: &RadarView,
+        context: &mut NavigationContext,
+    ) -> Option<RelativeDirection> {
+        if let Some(direction) = reachable_goal(radar, context.player_name) {
+            return Some(direction);
+        }
+
+        let position = context.player_state.position;
+        let orientation = context.player_state.orientation;
+        let passages: Vec<(CardinalDirection, u8)> = legal_moves(radar)
+            .map(|direction| {
+                let side = orientation.rotate(direction);
+                (side, self.marks(position, side))
+            })
+            .collect();
+        println!(
+            "[DEBUG {}] Position: {:?}, arrivée par {:?}, passages (côté, marques): {:?}",
+            context.player_name, position, self.entry, passages
+        );
+
+        let Some(side) = self.choose(position, &passages) else {
+            // Un passage bloqué par un allié ou masqué reste à parcourir : il faut l'attendre.
+            let mut open = MOVE_PRIORITY
+                .into_iter()
+                .filter(|&direction| radar.passage(direction) == Passage::Open)
+                .peekable();
+            let explored = open.peek().is_some()
+                && open.all(|direction| self.marks(position, orientation.rotate(direction)) == 2);
+            if explored {
+                println!(
+                    "[{}] Tous les passages ont été parcourus deux fois",
+                    context.player_name
+                );
+                self.finished = true;
+            } else {
+                println!(
+                    "[DEBUG {}] Aucun passage praticable pour l'instant",
+                    context.player_name
+                );
+            }
+            return None;
+        };
+        let direction = side.relative_to(orientation);
+        context.player_state.last_direction = Some(direction);
+        Some(direction)
+    }
+
+    fn finished(&self) -> bool {
+        self.finished
+    }
+}
@@ -0,0 +1,136 @@
+use super::{reachable_goal, ActionResult, NavigationContext, NavigationStrategy};
+use crate::direction::{CardinalDirection, RelativeDirection};
+use crate::radar::RadarView;
+
+/// La cellule voisine dans `direction` est libre et accessible.
+fn is_passable(radar: &RadarView, direction: RelativeDirection) -> bool {
+    radar.neighbour(direction).is_free() && radar.is_reachable(direction)
+}
+
+/// Suivi de mur : le joueur garde toujours une main (`Left` ou `Right`) contre le mur.
+///
+/// Dans un labyrinthe parfait, le suivi de mur finit par passer par toutes les cellules.
+pub struct WallFollower {
+    hand: RelativeDirection,
+}
+
+impl WallFollower {
+    pub fn left_hand() -> Self {
+        Self {
+            hand: RelativeDirection::Left,
+        }
+    }
+
+    pub fn right_hand() -> Self {
+        Self {
+            hand: RelativeDirection::Right,
+        }
+    }
+}
+
+impl NavigationStrategy for WallFollower {
+    fn decide(
+        &mut self,
+        radar: &RadarView,
+        context: &mut NavigationContext,
+    ) -> Option<RelativeDirection> {
+        if let Some(direction) = reachable_goal(radar, context.player_name) {
+            return Some(direction);
+        }
+
+        let order = [
+            self.hand,
+            RelativeDirection::Front,
+            self.hand.invert(),
+            RelativeDirection::Back,
+        ];
+        let direction = order
+            .into_iter()
+            .find(|&direction| is_passable(radar, direction))?;
+        context.player_state.last_direction = Some(direction);
+        Some(direction)
+    }
+}
+
+/// Algorithme de Pledge : le joueur avance tout droit dans une direction de référence, et
+/// contourne chaque obstacle main droite au mur jusqu'à ce que la somme des virages pris
+/// soit revenue à zéro.
+///
+/// Compter les virages (et non comparer l'orientation à la direction de référence) évite
+/// de quitter un obstacle trop tôt et de tourner indéfiniment autour d'un îlot.
+#[derive(Debug, Clone, Default)]
+pub struct Pledge {
+    /// Direction de référence : l'orientation du joueur à sa première décision
+    heading: Option<CardinalDirection>,
+    /// Somme des quarts de tour confirmés (positive dans le sens horaire)
+    turns: i32,
+}
+
+impl Pledge {
+    /// Quarts de tour effectués par un déplacement. En suivi main droite, un demi-tour
+    /// correspond à deux virages à gauche.
+    fn quarter_turns(movement: RelativeDirection) -> i32 {
+        match movement {
+            RelativeDirection::Front => 0,
+            RelativeDirection::Right => 1,
+            RelativeDirection::Left => -1,
+            RelativeDirection::Back => -2,
+        }
+    }
+
+    pub fn turns(&self) -> i32 {
+        self.turns
+    }
+}
+
+impl NavigationStrategy for Pledge {
+    fn observe_action_result(&mut self, result: ActionResult) {
+        if let ActionResult::Confirmed(movement) = result {
+            self.turns += Self::quarter_turns(movement);
+        }
+    }
+
+    fn decide(
+        &mut self,
+        radar: &RadarView,
+        context: &mut NavigationContext,
+    ) -> Option<RelativeDirection> {
+        if let Some(direction) = reachable_goal(radar, context.player_name) {
+            return Some(direction);
+        }
+
+        let orientation = context.player_state.orientation;
+        let heading = *self.heading.get_or_insert(orientation);
+        let straight = heading.relative_to(orientation);
+
+        let order: &[RelativeDirection] = if self.turns != 0 {
+            // Contournement de l'obstacle, main droite au mur
+            &[
+                RelativeDirection::Right,
+                RelativeDirection::Front,
+                RelativeDirection::Left,
+                RelativeDirection::Back,
+            ]
+        } else if is_passable(radar, straight) {
+            &[straight]
+        } else {
+            // Obstacle rencontré : on tourne à gauche pour le garder à droite
+            &[
+                RelativeDirection::Left,
+                RelativeDirection::Back,
+                RelativeDirection::Right,
+            ]
+        };
+
+        let direction = order
+            .iter()
+            .copied()
+            .find(|&direction| is_passable(radar, direction))?;
+        println!(
+            "[DEBUG {}] Pledge : cap {:?}, virages {}, direction {}",
+            context.player_name, heading, self.turns, direction
+        );
+        context.player_state.last_direction = Some(direction);
+        Some(direction)
+    }
+}
@@ -0,0 +1,183 @@
+use crate::direction::{CardinalDirection, RelativeDirection};
+use crate::map::MazeMap;
+use crate::player::Position;
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+
+/// Chemin le plus court de `start` jusqu'à la première cellule vérifiant `is_target`,
+/// en ne passant que par des passages ouverts de `map` (parcours en largeur).
+///
+/// Le chemin renvoyé commence par `start` et se termine sur la cellule cible.
+pub fn bfs(
+    map: &MazeMap,
+    start: Position,
+    is_target: impl Fn(Position) -> bool,
+) -> Option<Vec<Position>> {
+    bfs_avoiding(map, start, &HashSet::new(), is_target)
+}
+
+/// Comme [`bfs`], sans jamais passer par les cellules de `blocked`
+/// (occupées par un autre joueur, refusées par le serveur, ...).
+pub fn bfs_avoiding(
+    map: &MazeMap,
+    start: Position,
+    blocked: &HashSet<Position>,
+    is_target: impl Fn(Position) -> bool,
+) -> Option<Vec<Position>> {
+    let mut came_from: HashMap<Position, Position> = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+    came_from.insert(start, start);
+
+    while let Some(position) = queue.pop_front() {
+        if is_target(position) {
+            return Some(reconstruct(&came_from, start, position));
+        }
+        for (_, next) in map.open_neighbours(position) {
+            if blocked.contains(&next) {
+                continue;
+            }
+            if let Entry::Vacant(entry) = came_from.entry(next) {
+                entry.insert(position);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// Arbre des plus courts chemins depuis une cellule de départ, obtenu par parcours en largeur.
+#[derive(Debug, Clone)]
+pub struct SearchTree {
+    start: Position,
+    came_from: HashMap<Position, Position>,
+    distances: HashMap<Position, u32>,
+}
+
+impl SearchTree {
+    /// Explore toutes les cellules atteignables depuis `start` sans passer par `blocked`.
+    pub fn new(map: &MazeMap, start: Position, blocked: &HashSet<Position>) -> Self {
+        let mut came_from = HashMap::from([(start, start)]);
+        let mut distances = HashMap::from([(start, 0)]);
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(position) = queue.pop_front() {
+            let distance = distances[&position] + 1;
+            for (_, next) in map.open_neighbours(position) {
+                if blocked.contains(&next) || came_from.contains_key(&next) {
+                    continue;
+                }
+                came_from.insert(next, position);
+                distances.insert(next, distance);
+                queue.push_back(next);
+            }
+        }
+        Self {
+            start,
+            came_from,
+            distances,
+        }
+    }
+
+    /// Cellules atteignables et leur distance au départ.
+    pub fn distances(&self) -> impl Iterator<Item = (Position, u32)> + '_ {
+        self.distances
+            .iter()
+            .map(|(position, distance)| (*position, *distance))
+    }
+
+    /// Plus court chemin du départ jusqu'à `end`, si elle est atteignable.
+    pub fn path_to(&self, end: Position) -> Option<Vec<Position>> {
+        self.came_from
+            .contains_key(&end)
+            .then(|| reconstruct(&self.came_from, self.start, end))
+    }
+}
+
+/// Chemin le plus court entre `start` et `goal` par parcours en largeur.
+pub fn shortest_path(map: &MazeMap, start: Position, goal: Position) -> Option<Vec<Position>> {
+    bfs(map, start, |position| position == goal)
+}
+
+/// Chemin le plus court entre `start` et `goal` par A*, guidé par la distance de Manhattan,
+/// sans jamais passer par les cellules de `blocked` (comme [`bfs_avoiding`]).
+pub fn a_star(
+    map: &MazeMap,
+    start: Position,
+    blocked: &HashSet<Position>,
+    goal: Position,
+) -> Option<Vec<Position>> {
+    let mut came_from: HashMap<Position, Position> = HashMap::new();
+    let mut cost: HashMap<Position, u32> = HashMap::from([(start, 0)]);
+    // Tas min sur (coût estimé, heuristique, x, y) : à égalité, la cellule la plus proche du but
+    let mut open = BinaryHeap::new();
+    open.push(Reverse((
+        start.manhattan(goal),
+        start.manhattan(goal),
+        start.x(),
+        start.y(),
+    )));
+    came_from.insert(start, start);
+
+    while let Some(Reverse((_, _, x, y))) = open.pop() {
+        let position = Position::new(x, y);
+        if position == goal {
+            return Some(reconstruct(&came_from, start, goal));
+        }
+        let next_cost = cost[&position] + 1;
+        for (_, next) in map.open_neighbours(position) {
+            if blocked.contains(&next) {
+                continue;
+            }
+            if cost.get(&next).is_none_or(|&known| next_cost < known) {
+                cost.insert(next, next_cost);
+                came_from.insert(next, position);
+                let heuristic = next.manhattan(goal);
+                open.push(Reverse((
+                    next_cost + heuristic,
+                    heuristic,
+                    next.x(),
+                    next.y(),
+                )));
+            }
+        }
+    }
+    None
+}
+
+fn reconstruct(
+    came_from: &HashMap<Position, Position>,
+    start: Position,
+    end: Position,
+) -> Vec<Position> {
+    let mut path = vec![end];
+    let mut current = end;
+    while current != start {
+        current = came_from[&current];
+        path.push(current);
+    }
+    path.reverse();
+    path
+}
+
+/// Convertit un chemin de cellules voisines en déplacements relatifs, pour un joueur
+/// initialement orienté selon `orientation`.
+///
+/// Après chaque pas, le joueur fait face à la direction prise. Renvoie `None` si deux
+/// cellules consécutives du chemin ne sont pas voisines.
+pub fn to_relative_moves(
+    path: &[Position],
+    orientation: CardinalDirection,
+) -> Option<Vec<RelativeDirection>> {
+    let mut facing = orientation;
+    path.windows(2)
+        .map(|step| {
+            let side = CardinalDirection::ALL
+                .into_iter()
+                .find(|&side| step[0] + side == step[1])?;
+            let movement = side.relative_to(facing);
+            facing = side;
+            Some(movement)
+        })
+        .collect()
+}
@@ -1,41 +1,81 @@
+use crate::codec::{FrameError, FrameWriter, FramedStream};
+use crate::config::SecretWaitPolicy;
+use crate::direction::{CardinalDirection, RelativeDirection};
 use crate::game::GameState;
-use rand::seq::IndexedRandom;
-use rand::rng;
+use crate::protocol::{
+    Action, ActionError, ClientMessage, RegistrationError, ServerMessage, SubscribePlayer,
+    SubscribePlayerResult,
+};
+use crate::radar::{Cell, CellEntity, CellNature, Passage, RadarView};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::io::{Read, Write, Error};
-use std::net::TcpStream;
-use std::sync::Arc;
+use std::fmt;
 use std::fs::{File, OpenOptions};
+use std::io::{Error, Write};
+use std::sync::Arc;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Position {
     x: i32,
     y: i32,
 }
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// État d'un joueur tel qu'il est sauvegardé sur disque.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SavedState {
     pub position: Position,
+    pub orientation: Orientation,
+    /// Compteurs de visites, triés par position
+    pub visited: Vec<(Position, u8)>,
+    pub last_direction: Option<RelativeDirection>,
 }
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Orientation {
-    North,
-    East,
-    South,
-    West,
-}
 
+/// Orientation du joueur : la direction absolue vers laquelle il fait face.
+pub type Orientation = CardinalDirection;
+
+#[derive(Debug, Clone)]
 pub struct PlayerState {
     pub position: Position,
     pub visited: HashMap<Position, u8>,
-    pub last_direction: Option<&'static str>,
+    pub last_direction: Option<RelativeDirection>,
     pub orientation: Orientation,
+    /// Déplacement appliqué localement mais pas encore accepté par le serveur.
+    pub pending_move: Option<PendingMove>,
+    /// Dernière erreur d'action renvoyée par le serveur pour ce joueur.
+    pub last_action_error: Option<ActionError>,
+}
+
+/// Instantané de l'état du joueur pris avant un déplacement non confirmé.
+#[derive(Debug, Clone, Copy)]
+pub struct PendingMove {
+    pub movement: RelativeDirection,
+    pub previous_position: Position,
+    pub previous_orientation: Orientation,
+    /// Nombre de visites de la case d'arrivée avant le déplacement (`None` si jamais visitée).
+    pub previous_visits: Option<u8>,
 }
 
 impl Position {
     pub fn new(x: i32, y: i32) -> Self {
         Self { x, y }
     }
+
+    pub fn x(&self) -> i32 {
+        self.x
+    }
+
+    pub fn y(&self) -> i32 {
+        self.y
+    }
+
+    /// Cellule voisine dans la direction absolue `direction` (le nord vers les `y` décroissants).
+    pub fn neighbour(&self, direction: Orientation) -> Position {
+        *self + direction
+    }
+
+    /// Distance de Manhattan entre deux cellules.
+    pub fn manhattan(&self, other: Position) -> u32 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
 }
 /// Structure pour stocker l'historique des déplacements d'un joueur.
 pub struct MovementLog {
@@ -55,8 +95,8 @@ impl MovementLog {
     /// # Arguments
     ///
     /// * `position` - La position actuelle du joueur après son déplacement.
-    /// * `movement` - Le mouvement effectué ("Front", "Back", etc.).
-    pub fn log_movement(&self, position: &Position, movement: &str) {
+    /// * `movement` - Le mouvement effectué.
+    pub fn log_movement(&self, position: &Position, movement: RelativeDirection) {
         let filename = format!("{}_movements.log", self.player_name);
 
         let mut file = OpenOptions::new()
@@ -89,160 +129,123 @@ impl std::hash::Hash for Position {
     }
 }
 
-impl PlayerState {
-    /// Calcule la nouvelle position du joueur en fonction du mouvement spécifié.
-    ///
-    /// # Arguments
-    ///
-    /// * `movement` - Une chaîne représentant le mouvement à effectuer ("Front", "Back", "Left", "Right").
-    ///
-    /// # Retourne
-    ///
-    /// * `Position` - La nouvelle position après le déplacement.
-    pub fn compute_new_position(&self, movement: &str) -> Position {
-        let mut new_pos = self.position;
-
-        match movement {
-            "Front" => match self.orientation {
-                Orientation::North => new_pos.y -= 1,
-                Orientation::South => new_pos.y += 1,
-                Orientation::East => new_pos.x += 1,
-                Orientation::West => new_pos.x -= 1,
-            },
-            "Back" => match self.orientation {
-                Orientation::North => new_pos.y += 1,
-                Orientation::South => new_pos.y -= 1,
-                Orientation::East => new_pos.x -= 1,
-                Orientation::West => new_pos.x += 1,
-            },
-            "Left" => match self.orientation {
-                Orientation::North => new_pos.x -= 1,
-                Orientation::South => new_pos.x += 1,
-                Orientation::East => new_pos.y -= 1,
-                Orientation::West => new_pos.y += 1,
-            },
-            "Right" => match self.orientation {
-                Orientation::North => new_pos.x += 1,
-                Orientation::South => new_pos.x -= 1,
-                Orientation::East => new_pos.y += 1,
-                Orientation::West => new_pos.y -= 1,
-            },
-            _ => (),
-        }
+/// Translation d'une position, par exemple d'un repère de carte à un autre.
+impl std::ops::Add for Position {
+    type Output = Position;
 
-        new_pos
+    fn add(self, offset: Position) -> Position {
+        Position::new(self.x + offset.x, self.y + offset.y)
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-#[allow(non_snake_case)]
-pub struct RegisterTeam {
-    pub RegisterTeam: RegisterTeamBody,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct RegisterTeamBody {
-    pub name: String,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct RegisterTeamResultWrapper {
-    #[serde(rename = "RegisterTeamResult")]
-    pub result: RegisterTeamResult,
-}
+impl std::ops::Sub for Position {
+    type Output = Position;
 
-#[derive(Serialize, Deserialize, Debug)]
-pub enum RegisterTeamResult {
-    #[serde(rename = "Ok")]
-    Ok {
-        expected_players: u8,
-        registration_token: String,
-    },
-    #[serde(rename = "Err")]
-    Err(String),
+    fn sub(self, offset: Position) -> Position {
+        Position::new(self.x - offset.x, self.y - offset.y)
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-#[allow(non_snake_case)]
-pub struct SubscribePlayer {
-    pub SubscribePlayer: SubscribePlayerBody,
-}
+impl PlayerState {
+    pub fn new(position: Position, orientation: Orientation) -> Self {
+        Self {
+            position,
+            visited: HashMap::new(),
+            last_direction: None,
+            orientation,
+            pending_move: None,
+            last_action_error: None,
+        }
+    }
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct SubscribePlayerBody {
-    pub name: String,
-    pub registration_token: String,
-}
+    /// Prend un instantané de l'état confirmé du joueur, pour la sauvegarde.
+    ///
+    /// Un déplacement encore en attente n'est pas pris en compte.
+    pub fn snapshot(&self) -> SavedState {
+        let mut state = self.clone();
+        state.rollback_move();
+
+        let mut visited: Vec<(Position, u8)> = state.visited.into_iter().collect();
+        visited.sort_by_key(|(pos, _)| (pos.x, pos.y));
+        SavedState {
+            position: state.position,
+            orientation: state.orientation,
+            visited,
+            last_direction: state.last_direction,
+        }
+    }
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct SubscribePlayerResultWrapper {
-    #[serde(rename = "SubscribePlayerResult")]
-    result: SubscribePlayerResult,
-}
+    /// Reconstruit l'état d'un joueur à partir d'une sauvegarde.
+    pub fn restore(saved: &SavedState) -> Self {
+        let mut state = Self::new(saved.position, saved.orientation);
+        state.visited = saved.visited.iter().copied().collect();
+        state.last_direction = saved.last_direction;
+        state
+    }
 
-#[derive(Serialize, Deserialize, Debug)]
-pub enum SubscribePlayerResult {
-    #[serde(rename = "Ok")]
-    Ok,
-    #[serde(rename = "Err")]
-    Err(String),
-}
+    /// Valide le déplacement en attente une fois que le serveur l'a accepté, et l'enregistre dans le log.
+    pub fn confirm_move(&mut self, logger: &MovementLog) -> Option<PendingMove> {
+        let pending = self.pending_move.take()?;
+        logger.log_movement(&self.position, pending.movement);
+        Some(pending)
+    }
 
-pub fn move_player(player_state: &mut PlayerState, movement: &str, logger: &MovementLog) {
-    let mut new_pos = player_state.position;
-
-    match movement {
-        "Front" => match player_state.orientation {
-            Orientation::North => new_pos.y -= 1,
-            Orientation::South => new_pos.y += 1,
-            Orientation::East => new_pos.x += 1,
-            Orientation::West => new_pos.x -= 1,
-        },
-        "Back" => {
-            match player_state.orientation {
-                Orientation::North => new_pos.y += 1,
-                Orientation::South => new_pos.y -= 1,
-                Orientation::East => new_pos.x -= 1,
-                Orientation::West => new_pos.x += 1,
+    /// Annule le déplacement en attente refusé par le serveur : position, orientation et
+    /// compteur de visites reviennent à leur état précédent.
+    pub fn rollback_move(&mut self) -> Option<PendingMove> {
+        let pending = self.pending_move.take()?;
+        match pending.previous_visits {
+            Some(visits) => {
+                self.visited.insert(self.position, visits);
             }
-            // 🔥 **Changer l'orientation après déplacement**
-            player_state.orientation = match player_state.orientation {
-                Orientation::North => Orientation::South,
-                Orientation::South => Orientation::North,
-                Orientation::East => Orientation::West,
-                Orientation::West => Orientation::East,
-            };
-        }
-        "Left" => {
-            match player_state.orientation {
-                Orientation::North => new_pos.x -= 1,
-                Orientation::South => new_pos.x += 1,
-                Orientation::East => new_pos.y -= 1,
-                Orientation::West => new_pos.y += 1,
-            }
-            player_state.orientation = match player_state.orientation {
-                Orientation::North => Orientation::West,
-                Orientation::South => Orientation::East,
-                Orientation::East => Orientation::North,
-                Orientation::West => Orientation::South,
-            };
-        }
-        "Right" => {
-            match player_state.orientation {
-                Orientation::North => new_pos.x += 1,
-                Orientation::South => new_pos.x -= 1,
-                Orientation::East => new_pos.y += 1,
-                Orientation::West => new_pos.y -= 1,
+            None => {
+                self.visited.remove(&self.position);
             }
-            player_state.orientation = match player_state.orientation {
-                Orientation::North => Orientation::East,
-                Orientation::South => Orientation::West,
-                Orientation::East => Orientation::South,
-                Orientation::West => Orientation::North,
-            };
         }
-        _ => (),
+        self.position = pending.previous_position;
+        self.orientation = pending.previous_orientation;
+        Some(pending)
+    }
+
+    /// Position confirmée par le serveur, sans tenir compte d'un déplacement en attente.
+    pub fn confirmed_position(&self) -> Position {
+        self.pending_move
+            .map_or(self.position, |pending| pending.previous_position)
+    }
+
+    /// Orientation confirmée par le serveur, sans tenir compte d'un déplacement en attente.
+    pub fn confirmed_orientation(&self) -> Orientation {
+        self.pending_move
+            .map_or(self.orientation, |pending| pending.previous_orientation)
+    }
+
+    /// Calcule la nouvelle position du joueur en fonction du mouvement spécifié.
+    ///
+    /// Le joueur se déplace d'une case dans la direction absolue correspondant à `movement`
+    /// vu depuis son orientation actuelle.
+    pub fn compute_new_position(&self, movement: RelativeDirection) -> Position {
+        self.position + self.orientation.rotate(movement)
+    }
+
+    /// Met à jour l'orientation du joueur : après un déplacement, il fait face à la direction prise.
+    pub fn update_orientation(&mut self, movement: RelativeDirection) {
+        self.orientation = self.orientation.rotate(movement);
     }
+}
+
+/// Applique localement un déplacement avant son envoi au serveur.
+///
+/// Le déplacement reste en attente jusqu'à sa confirmation (`PlayerState::confirm_move`)
+/// ou son annulation (`PlayerState::rollback_move`).
+pub fn move_player(player_state: &mut PlayerState, movement: RelativeDirection) {
+    let new_pos = player_state.compute_new_position(movement);
+    player_state.pending_move = Some(PendingMove {
+        movement,
+        previous_position: player_state.position,
+        previous_orientation: player_state.orientation,
+        previous_visits: player_state.visited.get(&new_pos).copied(),
+    });
+    player_state.update_orientation(movement);
 
     player_state.position = new_pos;
 
@@ -255,186 +258,150 @@ pub fn move_player(player_state: &mut PlayerState, movement: &str, logger: &Move
         "[DEBUG] Nouvelle position: {:?}, Visites: {}",
         player_state.position, visit_count
     );
+}
+
+/// Erreurs possibles lors de l'inscription d'un joueur.
+#[derive(Debug)]
+pub enum SubscribeError {
+    /// Échec de connexion ou d'échange avec le serveur.
+    Transport(FrameError),
+    /// Le serveur a refusé l'inscription.
+    Rejected(RegistrationError),
+    /// Le serveur a répondu autre chose qu'un `SubscribePlayerResult`.
+    UnexpectedResponse(String),
+}
+
+impl SubscribeError {
+    /// Indique si une nouvelle tentative a une chance d'aboutir.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            SubscribeError::Transport(_) | SubscribeError::UnexpectedResponse(_) => true,
+            SubscribeError::Rejected(_) => false,
+        }
+    }
+}
 
-    logger.log_movement(&player_state.position, movement);
+impl fmt::Display for SubscribeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubscribeError::Transport(e) => write!(f, "{}", e),
+            SubscribeError::Rejected(e) => write!(f, "Inscription refusée : {}", e),
+            SubscribeError::UnexpectedResponse(response) => {
+                write!(f, "Réponse inattendue à SubscribePlayer : {}", response)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SubscribeError {}
 
+impl From<FrameError> for SubscribeError {
+    fn from(e: FrameError) -> Self {
+        SubscribeError::Transport(e)
+    }
 }
 
+/// Inscrit un joueur auprès du serveur et vérifie le `SubscribePlayerResult` reçu.
 pub fn subscribe_player(
-    stream: &mut TcpStream,
+    connection: &mut FramedStream,
     player_name: &str,
     registration_token: &str,
-) -> Result<(), String> {
-    let message = serde_json::json!({
-        "SubscribePlayer": {
-            "name": player_name,
-            "registration_token": registration_token,
-        }
+) -> Result<(), SubscribeError> {
+    let message = ClientMessage::SubscribePlayer(SubscribePlayer {
+        name: player_name.to_string(),
+        registration_token: registration_token.to_string(),
     });
 
-    let serialized_message = serde_json::to_string(&message)
-        .map_err(|e| format!("Failed to serialize message: {}", e))?;
-    let message_length = serialized_message.len() as u32;
-
-    let mut buffer = vec![];
-    buffer.extend(&message_length.to_le_bytes());
-    buffer.extend(serialized_message.as_bytes());
-
-    stream
-        .write_all(&buffer)
-        .map_err(|e| format!("Failed to send SubscribePlayer: {}", e))?;
+    connection.writer.send(&message)?;
     println!("SubscribePlayer message sent for {}", player_name);
 
-    let mut size_buffer = [0; 4];
-    stream
-        .read_exact(&mut size_buffer)
-        .map_err(|e| format!("Failed to read SubscribePlayer response size: {}", e))?;
-    let response_size = u32::from_le_bytes(size_buffer) as usize;
-
-    let mut response_buffer = vec![0; response_size];
-    stream
-        .read_exact(&mut response_buffer)
-        .map_err(|e| format!("Failed to read SubscribePlayer response: {}", e))?;
-    let response = String::from_utf8_lossy(&response_buffer);
-    println!("SubscribePlayer response: {}", response);
+    let response: ServerMessage = connection.reader.recv()?;
+    println!("SubscribePlayer response: {:?}", response);
 
-    Ok(())
-}
-
-pub fn process_blocks(decoded: &str) -> (Vec<String>, Vec<String>, Vec<String>) {
-    let parts: Vec<&str> = decoded.split(' ').collect();
-
-    let horizontal_passages = &parts[0..3];
-    let vertical_passages = &parts[3..6];
-    let cells = &parts[6..];
-
-    let inverted_horizontal = horizontal_passages
-        .iter()
-        .rev()
-        .cloned()
-        .collect::<Vec<&str>>()
-        .join(" ");
-    let inverted_vertical = vertical_passages
-        .iter()
-        .rev()
-        .cloned()
-        .collect::<Vec<&str>>()
-        .join(" ");
-
-    let parse_2bits = |binary_str: &str| -> Vec<String> {
-        binary_str
-            .chars()
-            .collect::<Vec<char>>()
-            .chunks(2)
-            .map(|chunk| chunk.iter().collect::<String>())
-            .collect()
-    };
-
-    let horizontal_lines: Vec<String> = inverted_horizontal
-        .split_whitespace()
-        .flat_map(parse_2bits)
-        .collect();
-
-    let vertical_lines: Vec<String> = inverted_vertical
-        .split_whitespace()
-        .flat_map(parse_2bits)
-        .collect();
-
-    let formatted_vertical_lines: Vec<String> = vertical_lines
-        .chunks(4)
-        .map(|chunk| chunk.join(" "))
-        .collect();
-
-    let concatenated_cells = cells.join("");
-    let formatted_cells = vec![
-        concatenated_cells[0..3].to_string(),
-        concatenated_cells[3..6].to_string(),
-        concatenated_cells[6..9].to_string(),
-    ];
-
-    (
-        horizontal_lines
-            .chunks(3)
-            .map(|chunk| chunk.join(" "))
-            .collect(),
-        formatted_vertical_lines,
-        formatted_cells,
-    )
+    match response {
+        ServerMessage::SubscribePlayerResult(SubscribePlayerResult::Ok) => Ok(()),
+        ServerMessage::SubscribePlayerResult(SubscribePlayerResult::Err(e)) => {
+            Err(SubscribeError::Rejected(e))
+        }
+        other => Err(SubscribeError::UnexpectedResponse(format!("{:?}", other))),
+    }
 }
 
-pub fn display_radar_view(horizontal: &[String], vertical: &[String], cells: &[String]) {
+/// Affiche la vue radar dans le terminal.
+pub fn display_radar_view(radar: &RadarView) {
     println!("\nVisualisation de la radar view :\n");
 
-    for i in 0..3 {
-        // Afficher les passages horizontaux
-        if i < horizontal.len() {
-            let mut previous_passage = None;
-            for passage in horizontal[i].split_whitespace() {
-                match passage {
-                    "00" => {
-                        if let Some("01") | Some("10") = previous_passage {
-                            print!("•#"); // Si la valeur précédente est "01" ou "10"
-                        } else {
-                            print!("##"); // Sinon
-                        }
-                    }
-                    "01" => print!("• "), // Open
-                    "10" => print!("•━"), // Wall
-                    _ => print!("  "),
-                } 
-                previous_passage = Some(passage);
-            }
-            println!("#");
-        }
+    for row in 0..3 {
+        display_horizontal_passages(radar, row);
 
         // Afficher les passages verticaux et les cellules
-        if i < vertical.len() && i < cells.len() {
-            for (v, c) in vertical[i].split_whitespace().zip(cells[i].chars()) {
-                match v {
-                    "00" => print!("#"), // Undefined
-                    "01" => print!(" "), // Open
-                    "10" => print!("┃"), // Wall
-                    _ => print!("#"),
-                }
-                match c {
-                    '0' => print!(" "), // Rien (Empty)
-                    '1' => print!("P"), // Friendly player
-                    '2' => print!("O"), // Opponent
-                    '3' => print!("M"), // Monster
-                    '4' => print!("H"), // Hint
-                    '8' => print!("G"), // Goal
-                    'B' => print!("*"), // Goal with Player
-                    'F' => print!("#"), // Undefined
-                    _ => print!("#"),
-                }
-            }
-            match vertical[i].split_whitespace().last() {
-                Some("01") => println!(" "),
-                Some("10") => println!("┃"),
-                _ => println!("#"),
+        for column in 0..3 {
+            match radar.vertical_passage(row, column) {
+                Passage::Undefined => print!("#"),
+                Passage::Open => print!(" "),
+                Passage::Wall => print!("┃"),
             }
+            print!("{}", cell_symbol(radar.cell(row, column)));
+        }
+        match radar.vertical_passage(row, 3) {
+            Passage::Open => println!(" "),
+            Passage::Wall => println!("┃"),
+            Passage::Undefined => println!("#"),
         }
     }
 
     // Dernière ligne des passages horizontaux
-    if let Some(last) = horizontal.last() {
-        let mut previous_passage = None;
-        for passage in last.split_whitespace() {
-            match passage {
-                "00" => {
-                    if let Some("01") | Some("10") = previous_passage {
-                        print!("•#"); // Si la valeur précédente est "01" ou "10"
-                    } else {
-                        print!("##"); // Sinon
-                    }
+    display_horizontal_passages(radar, 3);
+}
+
+fn display_horizontal_passages(radar: &RadarView, row: usize) {
+    let mut previous_passage = None;
+    for column in 0..3 {
+        let passage = radar.horizontal_passage(row, column);
+        match passage {
+            Passage::Undefined => {
+                if let Some(Passage::Open) | Some(Passage::Wall) = previous_passage {
+                    print!("•#"); // Si la valeur précédente est définie
+                } else {
+                    print!("##"); // Sinon
                 }
-                "01" => print!("• "), // Open
-                "10" => print!("•━"), // Wall
-                _ => print!("##"),
             }
-            previous_passage = Some(passage);
+            Passage::Open => print!("• "),
+            Passage::Wall => print!("•━"),
         }
-        println!("#");
+        previous_passage = Some(passage);
+    }
+    println!("#");
+}
+
+fn cell_symbol(cell: Cell) -> char {
+    match cell {
+        Cell::Undefined => '#',
+        Cell::Known {
+            nature: CellNature::Goal,
+            entity: CellEntity::None,
+        } => 'G',
+        Cell::Known {
+            nature: CellNature::Goal,
+            ..
+        } => '*', // Sortie occupée
+        Cell::Known {
+            entity: CellEntity::Ally,
+            ..
+        } => 'P',
+        Cell::Known {
+            entity: CellEntity::Enemy,
+            ..
+        } => 'O',
+        Cell::Known {
+            entity: CellEntity::Monster,
+            ..
+        } => 'M',
+        Cell::Known {
+            nature: CellNature::Hint,
+            ..
+        } => 'H',
+        Cell::Known { .. } => ' ',
     }
 }
 
@@ -442,288 +409,76 @@ pub fn display_radar_view(horizontal: &[String], vertical: &[String], cells: &[S
 ///
 /// # Arguments
 ///
-/// * `stream` - Une référence mutable vers le flux TCP.
-/// * `direction` - La direction du déplacement ("Front", "Back", "Left", "Right").
+/// * `writer` - Le flux d'écriture des trames vers le serveur.
+/// * `direction` - La direction du déplacement, relative à l'orientation du joueur.
 /// * `player_name` - Le nom du joueur effectuant l'action.
 ///
 /// # Retourne
 ///
 /// * `Result<(), String>` - Un `Ok(())` si l'action est envoyée avec succès, sinon une `Err` avec un message d'erreur.
-pub fn send_move_action(
-    stream: &mut TcpStream,
-    direction: &str,
+pub fn send_move_action<W: Write>(
+    writer: &mut FrameWriter<W>,
+    direction: RelativeDirection,
     player_name: &str,
 ) -> Result<(), String> {
-    let action = serde_json::json!({
-        "Action": {
-            "MoveTo": direction
-        }
-    });
-
-    let serialized_message = serde_json::to_string(&action).map_err(|e| e.to_string())?;
-    let message_length = serialized_message.len() as u32;
-
-    // Vérifier la taille maximale du message avant d'envoyer
-    if message_length > 1_048_576 {
-        return Err(format!("Message size too large: {}", message_length));
-    }
+    let action = ClientMessage::Action(Action::MoveTo(direction));
 
-    let mut buffer = vec![];
-    buffer.extend(&message_length.to_le_bytes()); // Taille en little-endian
-    buffer.extend(serialized_message.as_bytes()); // Message JSON sérialisé
-    stream.write_all(&buffer).map_err(|e| e.to_string())?;
+    writer.send(&action).map_err(|e| e.to_string())?;
 
     println!("[{}] Move action sent: {}", player_name, direction);
     Ok(())
 }
-/// Détermine le prochain mouvement du joueur en fonction de l'algorithme de Trémaux.
-///
-/// # Arguments
-///
-/// * `player_state` - État du joueur, contenant sa position et son historique de visites.
-/// * `radar_data` - Données du radar indiquant les murs et les passages.
-/// * `cells` - Informations sur les cellules adjacentes.
-/// * `player_name` - Nom du joueur.
-///
-/// # Retourne
+/// Répond au challenge `SecretSumModulo` avec la somme des secrets de l'équipe.
 ///
-/// * Une `&'static str` indiquant la direction du mouvement ("Front", "Back", "Left", "Right").
-pub fn tremaux_decide_move(
-    player_state: &mut PlayerState,
-    cells: &[String],
-    player_name: &str,
-) -> &'static str {
-    let current_pos = player_state.position;
-
-    // Mise à jour du compteur de visites
-    let visit_count = player_state.visited.entry(current_pos).or_insert(0);
-    if *visit_count < u8::MAX {
-        *visit_count = visit_count.saturating_add(1);
-    }
-
-    println!(
-        "[DEBUG {}] Position actuelle: {:?}, Visites: {}",
-        player_name, current_pos, visit_count
-    );
-
-    let mut moves = Vec::new();
-    let front_cell = cells.first().and_then(|row| row.chars().nth(1));
-    let back_cell = cells.get(2).and_then(|row| row.chars().nth(1));
-    let left_cell = cells.get(1).and_then(|row| row.chars().next());
-    let right_cell = cells.get(1).and_then(|row| row.chars().nth(2));
-    println!(
-        "[DEBUG {}] Cellule Value: Front: {:?}, Back: {:?}, Left: {:?}, Right: {:?}",
-        player_name, front_cell, back_cell, left_cell, right_cell
-    );
-    if front_cell == Some('8') || front_cell == Some('9') || front_cell == Some('A') {
-        println!(
-            "[DEBUG {}] 🚪 Sortie détectée devant ! Se dirige vers: Front",
-            player_name
-        );
-        return "Front";
-    }
-    if back_cell == Some('8') || front_cell == Some('9') || front_cell == Some('A'){
-        println!(
-            "[DEBUG {}] 🚪 Sortie détectée derrière ! Se dirige vers: Back",
-            player_name
-        );
-        return "Back";
-    }
-    if left_cell == Some('8') || front_cell == Some('9') || front_cell == Some('A'){
-        println!(
-            "[DEBUG {}] 🚪 Sortie détectée à gauche ! Se dirige vers: Left",
-            player_name
-        );
-        return "Left";
-    }
-    if right_cell == Some('8') || front_cell == Some('9') || front_cell == Some('A'){
-        println!(
-            "[DEBUG {}] 🚪 Sortie détectée à droite ! Se dirige vers: Right",
-            player_name
-        );
-        return "Right";
-    }
-
-    if front_cell == Some('0') {
-        moves.push(("Front", player_state.compute_new_position("Front")));
-    }
-    if back_cell == Some('0') {
-        moves.push(("Back", player_state.compute_new_position("Back")));
-    }
-    if left_cell == Some('0') {
-        moves.push(("Left", player_state.compute_new_position("Left")));
-    }
-    if right_cell == Some('0') {
-        moves.push(("Right", player_state.compute_new_position("Right")));
-    }
-    println!(
-        "[DEBUG {}] Mouvements possibles: {:?}",
-        player_name,
-        moves
-            .iter()
-            .map(|(dir, pos)| format!(
-                "{} -> {:?} (visites: {})",
-                dir,
-                pos,
-                match player_state.visited.get(pos) {
-                    Some(visits) => visits,
-                    None => {
-                        eprintln!("[DEBUG] Pas de visites enregistrées pour la position {:?}", pos);
-                        &0
-                    }
-                }
-            ))
-            .collect::<Vec<_>>()
-    );
-
-    // **Prioriser la position avec le moins de visites**
-    if let Some((best_move, _)) = moves
-        .iter()
-        .min_by_key(|(_, pos)| match player_state.visited.get(pos) {
-            Some(visits) => *visits,
-            None => {
-                eprintln!("[DEBUG] Pas de visites enregistrées pour la position {:?}", pos);
-                0
-            }
-        })
-    {
-        player_state.last_direction = Some(*best_move);
-        return best_move;
-    }
-
-    let last_option = match moves.first() {
-        Some((direction, _)) => *direction,
-        None => {
-            eprintln!("[DEBUG] Aucun mouvement viable, retour par défaut à Back");
-            "Back"
-        }
-    };
-    player_state.last_direction = Some(last_option);
-
-    println!(
-        "[DEBUG {}] Aucune option optimale, derniern option: {}",
-        player_name, last_option
-    );
-    last_option
-}
-
-pub fn handle_secret_sum_modulo(
-    stream: &mut TcpStream,
+/// Attend, selon `policy`, que chaque joueur inscrit ait reçu un secret frais postérieur à
+/// `after_seq` (0 pour une première réponse, le numéro de la réponse refusée sinon).
+/// Retourne le numéro du dernier secret pris en compte, ou `None` si rien n'a été envoyé.
+pub fn handle_secret_sum_modulo<W: Write>(
+    writer: &mut FrameWriter<W>,
     player_name: &str,
     game_state: &Arc<GameState>,
+    policy: &SecretWaitPolicy,
     modulo: u64,
-) {
-    // On verrouille le mutex UNE SEULE FOIS et on stocke les valeurs localement
-    let known_secrets: Vec<u128> = {
-        let secrets = match game_state.secrets.lock() {
-            Ok(secrets) => secrets,
-            Err(e) => {
-                eprintln!("[DEBUG] Échec du verrouillage du mutex des secrets : {}", e);
-                return;
-            }
-        };
-        secrets.values().map(|&v| v as u128).collect()
+    after_seq: u64,
+) -> Option<u64> {
+    let Some(snapshot) = game_state.wait_for_secrets(policy, after_seq, player_name) else {
+        eprintln!("[DEBUG] Échec du verrouillage du mutex des secrets");
+        return None;
     };
-
-    // Calcul de la somme des secrets
-    let sum: u128 = known_secrets.iter().sum();
+    if !snapshot.missing.is_empty() {
+        eprintln!(
+            "[{}] Secrets manquants ou périmés après {} ms : {:?}",
+            player_name, policy.timeout_ms, snapshot.missing
+        );
+    }
 
     println!(
         "[{}] Known secrets before modulo: {:?}",
-        player_name, known_secrets
+        player_name, snapshot.values
     );
-    println!("[{}] Sum before modulo: {}", player_name, sum);
     println!("[{}] Modulo value: {}", player_name, modulo);
 
-    // Appliquer le modulo en u128 et reconvertir en u64
-    let result = (sum % modulo as u128) as u64;
-    println!("[{}] SecretSumModulo result: {}", player_name, result);
-
-    // envoi de la réponse au serveur la base
-    let response = serde_json::json!({
-        "Action": {
-            "SolveChallenge": {
-                "answer": result.to_string()
-            }
-        }
-    });
-
-    let serialized_response = match serde_json::to_string(&response) {
-        Ok(res) => res,
+    let result = match snapshot.sum_modulo(modulo) {
+        Ok(result) => result,
         Err(e) => {
-            eprintln!("[DEBUG] Échec de la sérialisation de la réponse : {}", e);
-            return;
+            eprintln!("[{}] SecretSumModulo : {}", player_name, e);
+            return None;
         }
     };
-    let message_length = serialized_response.len() as u32;
+    println!("[{}] SecretSumModulo result: {}", player_name, result);
 
-    let mut buffer = vec![];
-    buffer.extend(&message_length.to_le_bytes());
-    buffer.extend(serialized_response.as_bytes());
+    // envoi de la réponse au serveur la base
+    let response = ClientMessage::Action(Action::SolveChallenge {
+        answer: result.to_string(),
+    });
 
-    if let Err(e) = stream.write_all(&buffer) {
+    if let Err(e) = writer.send(&response) {
         eprintln!(
             "[{}] Failed to send SecretSumModulo response: {}",
             player_name, e
         );
-    } else {
-        println!("[{}] Sent SolveChallenge response: {}", player_name, result);
-    }
-}
-
-pub fn random_decide_move(
-    cells: &[String],
-    player_name: &str,
-) -> &'static str {
-    let mut rng = rng();
-    let mut moves = vec![];
-
-    let front_cell = cells.first().and_then(|row| row.chars().nth(1));
-    let back_cell = cells.get(2).and_then(|row| row.chars().nth(1));
-    let left_cell = cells.get(1).and_then(|row| row.chars().next());
-    let right_cell = cells.get(1).and_then(|row| row.chars().nth(2));
-
-    if front_cell == Some('8') || front_cell == Some('9') {
-        println!(
-            "[DEBUG {}] 🚪 Sortie détectée devant ! Se dirige vers: Front",
-            player_name
-        );
-        return "Front";
-    }
-    if back_cell == Some('8') || front_cell == Some('9') {
-        println!(
-            "[DEBUG {}] 🚪 Sortie détectée derrière ! Se dirige vers: Back",
-            player_name
-        );
-        return "Back";
+        return None;
     }
-    if left_cell == Some('8') || front_cell == Some('9') {
-        println!(
-            "[DEBUG {}] 🚪 Sortie détectée à gauche ! Se dirige vers: Left",
-            player_name
-        );
-        return "Left";
-    }
-    if right_cell == Some('8') || front_cell == Some('9') {
-        println!(
-            "[DEBUG {}] 🚪 Sortie détectée à droite ! Se dirige vers: Right",
-            player_name
-        );
-        return "Right";
-    }
-
-    if front_cell == Some('0') {
-        moves.push("Front");
-    }
-    if back_cell == Some('0') {
-        moves.push("Back");
-    }
-    if left_cell == Some('0') {
-        moves.push("Left");
-    }
-    if right_cell == Some('0') {
-        moves.push("Right");
-    }
-
-    moves.choose(&mut rng).copied().unwrap_or("Back")
+    println!("[{}] Sent SolveChallenge response: {}", player_name, result);
+    Some(snapshot.seq)
 }
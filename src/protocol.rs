@@ -0,0 +1,177 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+pub use crate::direction::RelativeDirection;
+
+/// Messages envoyés par le client au serveur.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum ClientMessage {
+    RegisterTeam(RegisterTeam),
+    SubscribePlayer(SubscribePlayer),
+    Action(Action),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RegisterTeam {
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SubscribePlayer {
+    pub name: String,
+    pub registration_token: String,
+}
+
+/// Action de jeu d'un joueur déjà inscrit.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    MoveTo(RelativeDirection),
+    SolveChallenge { answer: String },
+}
+
+/// Messages envoyés par le serveur au client.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum ServerMessage {
+    RegisterTeamResult(RegisterTeamResult),
+    SubscribePlayerResult(SubscribePlayerResult),
+    RadarView(String),
+    Hint(Hint),
+    Challenge(Challenge),
+    ActionError(ActionError),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum RegisterTeamResult {
+    Ok {
+        expected_players: u8,
+        registration_token: String,
+    },
+    Err(RegistrationError),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum SubscribePlayerResult {
+    Ok,
+    Err(RegistrationError),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegistrationError {
+    AlreadyRegistered,
+    InvalidName,
+    InvalidRegistrationToken,
+    TooManyPlayers,
+}
+
+impl fmt::Display for RegistrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Indices transmis par le serveur au cours de la partie.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum Hint {
+    RelativeCompass { angle: f32 },
+    GridSize { columns: u32, rows: u32 },
+    Secret(u64),
+    SOSHelper,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum Challenge {
+    SecretSumModulo(u64),
+    SOS,
+    /// Challenge inconnu du client, désigné par son type ; jamais envoyé au serveur.
+    #[serde(skip)]
+    Unsupported(String),
+}
+
+impl Challenge {
+    /// Types des challenges connus du client.
+    pub const KNOWN_KINDS: [&'static str; 2] = ["SecretSumModulo", "SOS"];
+
+    /// Type du challenge, tel qu'il apparaît dans le protocole.
+    pub fn kind(&self) -> &str {
+        match self {
+            Challenge::SecretSumModulo(_) => "SecretSumModulo",
+            Challenge::SOS => "SOS",
+            Challenge::Unsupported(kind) => kind,
+        }
+    }
+}
+
+/// Message `Challenge` dont le contenu n'est pas reconnu.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct UnknownChallenge {
+    #[serde(rename = "Challenge")]
+    challenge: serde_json::Value,
+}
+
+impl UnknownChallenge {
+    /// Type du challenge : la chaîne elle-même, ou la clé unique de l'objet.
+    fn kind(&self) -> Option<String> {
+        match &self.challenge {
+            serde_json::Value::String(kind) => Some(kind.clone()),
+            serde_json::Value::Object(fields) if fields.len() == 1 => fields.keys().next().cloned(),
+            _ => None,
+        }
+    }
+}
+
+/// Erreurs renvoyées par le serveur lorsqu'une action est refusée.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionError {
+    CannotPassThroughWall,
+    CannotPassThroughOpponent,
+    NoRunningChallenge,
+    SolveChallengeFirst,
+    InvalidChallengeSolution,
+}
+
+impl ActionError {
+    /// Indique si l'erreur signifie que le dernier déplacement n'a pas eu lieu.
+    pub fn rejects_move(&self) -> bool {
+        matches!(
+            self,
+            ActionError::CannotPassThroughWall
+                | ActionError::CannotPassThroughOpponent
+                | ActionError::SolveChallengeFirst
+        )
+    }
+}
+
+impl fmt::Display for ActionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Décode un message brut reçu du serveur.
+///
+/// # Retourne
+///
+/// * `Result<ServerMessage, String>` - Le message typé, ou une `Err` décrivant le contenu
+///   inconnu ou malformé. Un challenge d'un type inconnu est décodé en
+///   [`Challenge::Unsupported`] pour pouvoir être signalé ; un challenge d'un type connu
+///   mais au contenu invalide est une erreur.
+pub fn parse_server_message(bytes: &[u8]) -> Result<ServerMessage, String> {
+    serde_json::from_slice(bytes).or_else(|e| {
+        serde_json::from_slice::<UnknownChallenge>(bytes)
+            .ok()
+            .and_then(|unknown| unknown.kind())
+            // Un challenge connu qui ne se décode pas est malformé, pas inconnu.
+            .filter(|kind| !Challenge::KNOWN_KINDS.contains(&kind.as_str()))
+            .map(|kind| ServerMessage::Challenge(Challenge::Unsupported(kind)))
+            .ok_or_else(|| {
+                format!(
+                    "Message serveur inconnu ou malformé ({}) : {}",
+                    e,
+                    String::from_utf8_lossy(bytes)
+                )
+            })
+    })
+}
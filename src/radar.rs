@@ -0,0 +1,383 @@
+use crate::direction::{CardinalDirection, RelativeDirection};
+use crate::map::Edge;
+use crate::player::{Orientation, Position};
+use crate::utils::{decode_b64, encode_b64, B64Error};
+use std::fmt;
+
+/// Taille en octets d'une vue radar décodée : 3 octets de passages horizontaux,
+/// 3 octets de passages verticaux et 5 octets pour les 9 cellules.
+pub const RADAR_VIEW_BYTES: usize = 11;
+
+/// Passage entre deux cellules (ou en bordure de la vue radar).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Passage {
+    Undefined,
+    Open,
+    Wall,
+}
+
+/// Contenu fixe d'une cellule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CellNature {
+    None,
+    Hint,
+    Goal,
+}
+
+/// Entité présente sur une cellule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CellEntity {
+    None,
+    Ally,
+    Enemy,
+    Monster,
+}
+
+/// Cellule de la vue radar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Cell {
+    /// Cellule hors de vue (derrière un mur ou hors du labyrinthe).
+    Undefined,
+    Known {
+        nature: CellNature,
+        entity: CellEntity,
+    },
+}
+
+impl Cell {
+    pub const EMPTY: Cell = Cell::Known {
+        nature: CellNature::None,
+        entity: CellEntity::None,
+    };
+
+    /// La cellule contient la sortie.
+    pub fn is_goal(&self) -> bool {
+        matches!(
+            self,
+            Cell::Known {
+                nature: CellNature::Goal,
+                ..
+            }
+        )
+    }
+
+    /// La cellule est connue et libre de toute entité.
+    pub fn is_free(&self) -> bool {
+        matches!(
+            self,
+            Cell::Known {
+                entity: CellEntity::None,
+                ..
+            }
+        )
+    }
+}
+
+/// Erreurs de décodage d'une vue radar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RadarError {
+    /// La chaîne n'est pas un encodage base64 valide.
+    InvalidEncoding(B64Error),
+    /// Le nombre d'octets décodés ne correspond pas à une vue radar.
+    InvalidLength { expected: usize, actual: usize },
+    /// Valeur de passage sur 2 bits non définie par le protocole.
+    InvalidPassage(u8),
+    /// Valeur de cellule sur 4 bits non définie par le protocole.
+    InvalidCell(u8),
+}
+
+impl fmt::Display for RadarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RadarError::InvalidEncoding(e) => write!(f, "Encodage invalide : {}", e),
+            RadarError::InvalidLength { expected, actual } => write!(
+                f,
+                "Taille de vue radar invalide : {} octets au lieu de {}",
+                actual, expected
+            ),
+            RadarError::InvalidPassage(bits) => write!(f, "Passage invalide : {:02b}", bits),
+            RadarError::InvalidCell(bits) => write!(f, "Cellule invalide : {:04b}", bits),
+        }
+    }
+}
+
+impl std::error::Error for RadarError {}
+
+/// Vue radar 3x3 centrée sur le joueur, relative à son orientation (ligne 0 = devant).
+///
+/// * `horizontal` : 4 lignes de 3 passages, la ligne `r` borde le haut de la ligne de cellules `r`.
+/// * `vertical` : 3 lignes de 4 passages, la colonne `c` borde la gauche de la colonne de cellules `c`.
+/// * `cells` : 3 lignes de 3 cellules, le joueur est en `(1, 1)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RadarView {
+    pub horizontal: [Passage; 12],
+    pub vertical: [Passage; 12],
+    pub cells: [Cell; 9],
+}
+
+impl RadarView {
+    /// Décode une vue radar depuis la chaîne base64 envoyée par le serveur.
+    pub fn decode(encoded: &str) -> Result<Self, RadarError> {
+        let bytes = decode_b64(encoded).map_err(RadarError::InvalidEncoding)?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Encode la vue radar au format envoyé par le serveur.
+    pub fn encode(&self) -> String {
+        encode_b64(&self.to_bytes())
+    }
+
+    /// Représentation binaire sur 11 octets, inverse de `RadarView::from_bytes`.
+    pub fn to_bytes(&self) -> [u8; RADAR_VIEW_BYTES] {
+        let mut bytes = [0; RADAR_VIEW_BYTES];
+        bytes[0..3].copy_from_slice(&encode_passages(&self.horizontal));
+        bytes[3..6].copy_from_slice(&encode_passages(&self.vertical));
+        // Le dernier quartet, inutilisé, est laissé à zéro
+        for (i, cell) in self.cells.iter().enumerate() {
+            let bits = encode_cell(*cell);
+            bytes[6 + i / 2] |= if i % 2 == 0 { bits << 4 } else { bits };
+        }
+        bytes
+    }
+
+    /// Décode une vue radar depuis ses 11 octets.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, RadarError> {
+        if bytes.len() != RADAR_VIEW_BYTES {
+            return Err(RadarError::InvalidLength {
+                expected: RADAR_VIEW_BYTES,
+                actual: bytes.len(),
+            });
+        }
+
+        Ok(Self {
+            horizontal: decode_passages(&bytes[0..3])?,
+            vertical: decode_passages(&bytes[3..6])?,
+            cells: decode_cells(&bytes[6..11])?,
+        })
+    }
+
+    /// Passage horizontal au-dessus de la ligne de cellules `row` (0..=3), colonne `column` (0..3).
+    pub fn horizontal_passage(&self, row: usize, column: usize) -> Passage {
+        self.horizontal[row * 3 + column]
+    }
+
+    /// Passage vertical à gauche de la colonne de cellules `column` (0..=3), ligne `row` (0..3).
+    pub fn vertical_passage(&self, row: usize, column: usize) -> Passage {
+        self.vertical[row * 4 + column]
+    }
+
+    pub fn cell(&self, row: usize, column: usize) -> Cell {
+        self.cells[row * 3 + column]
+    }
+
+    /// Cellule voisine du joueur dans la direction relative `direction`.
+    pub fn neighbour(&self, direction: RelativeDirection) -> Cell {
+        self.cells[neighbour_index(direction)]
+    }
+
+    /// Passage entre la cellule du joueur et sa voisine dans la direction relative `direction`.
+    pub fn passage(&self, direction: RelativeDirection) -> Passage {
+        match direction {
+            RelativeDirection::Front => self.horizontal_passage(1, 1),
+            RelativeDirection::Back => self.horizontal_passage(2, 1),
+            RelativeDirection::Left => self.vertical_passage(1, 1),
+            RelativeDirection::Right => self.vertical_passage(1, 2),
+        }
+    }
+
+    /// La cellule voisine dans `direction` est connue et aucun mur ne l'en sépare.
+    pub fn is_reachable(&self, direction: RelativeDirection) -> bool {
+        self.passage(direction) == Passage::Open && self.neighbour(direction) != Cell::Undefined
+    }
+
+    /// Rend inaccessible la cellule voisine dans `direction`,
+    /// par exemple après un déplacement refusé par le serveur.
+    pub fn mask(&mut self, direction: RelativeDirection) {
+        self.cells[neighbour_index(direction)] = Cell::Undefined;
+    }
+
+    /// Copie de la vue dont les cellules occupées par un allié sont libérées : les
+    /// coéquipiers aperçus ont pu se déplacer depuis la réception du radar. Les ennemis et
+    /// les monstres restent en place.
+    pub fn without_allies(&self) -> Self {
+        let mut radar = self.clone();
+        for cell in &mut radar.cells {
+            if let Cell::Known { entity, .. } = cell {
+                if *entity == CellEntity::Ally {
+                    *entity = CellEntity::None;
+                }
+            }
+        }
+        radar
+    }
+}
+
+/// Vue radar ramenée dans le repère absolu de la carte (nord en haut, `y` croissant vers le sud).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorldView {
+    /// Les 9 cellules de la vue, avec leur position absolue.
+    pub cells: [(Position, Cell); 9],
+    /// Les 24 passages de la vue, désignés par leur arête absolue.
+    pub passages: [(Edge, Passage); 24],
+}
+
+impl WorldView {
+    pub fn cell(&self, position: Position) -> Option<Cell> {
+        self.cells
+            .iter()
+            .find(|(p, _)| *p == position)
+            .map(|(_, cell)| *cell)
+    }
+
+    /// Passage entre `position` et sa voisine dans la direction absolue `side`.
+    pub fn passage(&self, position: Position, side: CardinalDirection) -> Option<Passage> {
+        let edge = Edge::new(position, side);
+        self.passages
+            .iter()
+            .find(|(e, _)| *e == edge)
+            .map(|(_, passage)| *passage)
+    }
+}
+
+impl RadarView {
+    /// Tourne la vue radar selon l'orientation du joueur et la centre sur sa position.
+    ///
+    /// La ligne 0 du radar est devant le joueur : orienté à l'est, elle devient la colonne
+    /// de cellules à l'est de `position`.
+    pub fn to_world(&self, position: Position, orientation: Orientation) -> WorldView {
+        let front = orientation;
+        let left = orientation.rotate(RelativeDirection::Left);
+
+        let cells = std::array::from_fn(|i| {
+            let (row, column) = (i / 3, i % 3);
+            (
+                radar_to_world(position, orientation, row, column),
+                self.cell(row, column),
+            )
+        });
+
+        // Le passage horizontal (r, c) borde l'avant de la cellule (r, c), le passage
+        // vertical (r, c) borde sa gauche ; la ligne 3 et la colonne 3 sont hors de la vue.
+        let passages = std::array::from_fn(|i| {
+            if i < 12 {
+                let (row, column) = (i / 3, i % 3);
+                let cell = radar_to_world(position, orientation, row, column);
+                (Edge::new(cell, front), self.horizontal_passage(row, column))
+            } else {
+                let (row, column) = ((i - 12) / 4, (i - 12) % 4);
+                let cell = radar_to_world(position, orientation, row, column);
+                (Edge::new(cell, left), self.vertical_passage(row, column))
+            }
+        });
+
+        WorldView { cells, passages }
+    }
+}
+
+/// Position absolue de la cellule `(row, column)` d'une vue radar
+/// (le joueur en `(1, 1)`, la ligne 0 devant lui).
+fn radar_to_world(
+    position: Position,
+    orientation: Orientation,
+    row: usize,
+    column: usize,
+) -> Position {
+    let forward = 1 - row as i32;
+    let right = column as i32 - 1;
+
+    let (front_x, front_y) = orientation.offset();
+    let (right_x, right_y) = orientation.rotate(RelativeDirection::Right).offset();
+    Position::new(
+        position.x() + forward * front_x + right * right_x,
+        position.y() + forward * front_y + right * right_y,
+    )
+}
+
+fn neighbour_index(direction: RelativeDirection) -> usize {
+    match direction {
+        RelativeDirection::Front => 1,
+        RelativeDirection::Left => 3,
+        RelativeDirection::Right => 5,
+        RelativeDirection::Back => 7,
+    }
+}
+
+/// Les 12 passages sont lus sur un entier de 24 bits little-endian, des bits de poids fort
+/// vers les bits de poids faible.
+fn decode_passages(bytes: &[u8]) -> Result<[Passage; 12], RadarError> {
+    let value = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]);
+    let mut passages = [Passage::Undefined; 12];
+    for (i, passage) in passages.iter_mut().enumerate() {
+        *passage = match (value >> (22 - 2 * i)) & 0b11 {
+            0b00 => Passage::Undefined,
+            0b01 => Passage::Open,
+            0b10 => Passage::Wall,
+            bits => return Err(RadarError::InvalidPassage(bits as u8)),
+        };
+    }
+    Ok(passages)
+}
+
+fn encode_passages(passages: &[Passage; 12]) -> [u8; 3] {
+    let value = passages
+        .iter()
+        .enumerate()
+        .fold(0u32, |value, (i, passage)| {
+            let bits = match passage {
+                Passage::Undefined => 0b00,
+                Passage::Open => 0b01,
+                Passage::Wall => 0b10,
+            };
+            value | bits << (22 - 2 * i)
+        });
+    let [b0, b1, b2, _] = value.to_le_bytes();
+    [b0, b1, b2]
+}
+
+fn encode_cell(cell: Cell) -> u8 {
+    match cell {
+        Cell::Undefined => 0x0F,
+        Cell::Known { nature, entity } => {
+            let nature = match nature {
+                CellNature::None => 0b00,
+                CellNature::Hint => 0b01,
+                CellNature::Goal => 0b10,
+            };
+            let entity = match entity {
+                CellEntity::None => 0b00,
+                CellEntity::Ally => 0b01,
+                CellEntity::Enemy => 0b10,
+                CellEntity::Monster => 0b11,
+            };
+            nature << 2 | entity
+        }
+    }
+}
+
+/// Les 9 cellules occupent chacune 4 bits, dans l'ordre des octets, quartet de poids fort
+/// en premier. Les 2 bits de poids fort donnent la nature, les 2 autres l'entité.
+fn decode_cells(bytes: &[u8]) -> Result<[Cell; 9], RadarError> {
+    let mut cells = [Cell::Undefined; 9];
+    for (i, cell) in cells.iter_mut().enumerate() {
+        let byte = bytes[i / 2];
+        let bits = if i % 2 == 0 { byte >> 4 } else { byte & 0x0F };
+        if bits == 0x0F {
+            continue;
+        }
+
+        let nature = match bits >> 2 {
+            0b00 => CellNature::None,
+            0b01 => CellNature::Hint,
+            0b10 => CellNature::Goal,
+            _ => return Err(RadarError::InvalidCell(bits)),
+        };
+        let entity = match bits & 0b11 {
+            0b00 => CellEntity::None,
+            0b01 => CellEntity::Ally,
+            0b10 => CellEntity::Enemy,
+            _ => CellEntity::Monster,
+        };
+        *cell = Cell::Known { nature, entity };
+    }
+    Ok(cells)
+}
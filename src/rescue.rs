@@ -0,0 +1,124 @@
+use crate::map::MazeMap;
+use crate::planner::bfs;
+use crate::player::Position;
+use std::collections::{BTreeMap, HashMap};
+
+/// Nombre de coéquipiers envoyés au secours d'un joueur.
+const MAX_RESCUERS: usize = 2;
+
+/// Appel à l'aide d'un joueur bloqué par un challenge SOS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Distress {
+    /// Position du joueur au moment de l'appel
+    pub position: Position,
+    /// Coéquipiers en route vers lui
+    pub rescuers: Vec<String>,
+}
+
+/// Suivi des appels SOS de l'équipe et des coéquipiers envoyés à la rescousse.
+///
+/// Les secouristes sont choisis parmi les joueurs libres, du plus proche au plus éloigné
+/// en longueur de chemin sur la carte commune (à défaut de chemin connu, en distance de
+/// Manhattan). Toutes les positions sont dans le repère de cette carte.
+#[derive(Debug, Default)]
+pub struct Rescue {
+    /// Dernière position connue de chaque joueur
+    positions: HashMap<String, Position>,
+    /// Appels en cours, par joueur en détresse
+    distress: BTreeMap<String, Distress>,
+}
+
+impl Rescue {
+    pub fn update_position(&mut self, player_name: &str, position: Position) {
+        self.positions.insert(player_name.to_string(), position);
+    }
+
+    pub fn is_in_distress(&self, player_name: &str) -> bool {
+        self.distress.contains_key(player_name)
+    }
+
+    /// Joueur en détresse que `player_name` doit rejoindre, et sa position.
+    pub fn target(&self, player_name: &str) -> Option<(&str, Position)> {
+        self.distress
+            .iter()
+            .find(|(_, distress)| {
+                distress
+                    .rescuers
+                    .iter()
+                    .any(|rescuer| rescuer == player_name)
+            })
+            .map(|(name, distress)| (name.as_str(), distress.position))
+    }
+
+    /// Un joueur est libre s'il n'est ni en détresse, ni déjà en route vers un coéquipier.
+    fn is_available(&self, player_name: &str) -> bool {
+        !self.is_in_distress(player_name) && self.target(player_name).is_none()
+    }
+
+    /// Enregistre l'appel à l'aide de `player_name` et lui attribue les coéquipiers libres
+    /// les plus proches. Retourne les secouristes choisis.
+    pub fn raise(&mut self, map: &MazeMap, player_name: &str, position: Position) -> Vec<String> {
+        self.release(player_name);
+        self.positions.insert(player_name.to_string(), position);
+
+        let mut candidates: Vec<(bool, u32, &String)> = self
+            .positions
+            .iter()
+            .filter(|(name, _)| self.is_available(name) && name.as_str() != player_name)
+            .map(
+                |(name, &from)| match bfs(map, from, |cell| cell == position) {
+                    Some(path) => (false, path.len() as u32 - 1, name),
+                    None => (true, from.manhattan(position), name),
+                },
+            )
+            .collect();
+        candidates.sort();
+
+        let rescuers: Vec<String> = candidates
+            .into_iter()
+            .take(MAX_RESCUERS)
+            .map(|(_, _, name)| name.clone())
+            .collect();
+        self.distress.insert(
+            player_name.to_string(),
+            Distress {
+                position,
+                rescuers: rescuers.clone(),
+            },
+        );
+        rescuers
+    }
+
+    /// Ajoute `player_name` aux secouristes de l'appel en cours le plus proche de lui, par
+    /// exemple après un indice `SOSHelper`. Retourne le joueur à secourir et sa position.
+    pub fn volunteer(&mut self, player_name: &str) -> Option<(String, Position)> {
+        if let Some((name, position)) = self.target(player_name) {
+            return Some((name.to_string(), position));
+        }
+        if self.is_in_distress(player_name) {
+            return None;
+        }
+        let from = self.positions.get(player_name).copied();
+        let (name, distress) = self
+            .distress
+            .iter_mut()
+            .min_by_key(|(_, distress)| from.map_or(0, |from| from.manhattan(distress.position)))?;
+        distress.rescuers.push(player_name.to_string());
+        Some((name.clone(), distress.position))
+    }
+
+    /// Clôt l'appel à l'aide de `player_name`, ce qui libère ses secouristes.
+    pub fn resolve(&mut self, player_name: &str) -> Option<Distress> {
+        self.distress.remove(player_name)
+    }
+
+    /// Retire un joueur qui quitte la partie : son appel éventuel est clos, il ne fait
+    /// plus partie d'aucune équipe de secours et ne peut plus être choisi comme secouriste.
+    pub fn release(&mut self, player_name: &str) {
+        self.positions.remove(player_name);
+        self.distress.remove(player_name);
+        for distress in self.distress.values_mut() {
+            distress.rescuers.retain(|rescuer| rescuer != player_name);
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::{BTreeSet, HashMap};
+use std::time::{Duration, Instant};
+
+/// Secret reçu par un joueur, numéroté dans l'ordre de réception.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SecretValue {
+    pub value: u64,
+    /// Numéro de réception, croissant pour toute l'équipe
+    pub seq: u64,
+    /// Instant de réception ; `None` pour un secret repris d'une sauvegarde
+    pub received_at: Option<Instant>,
+}
+
+impl SecretValue {
+    /// Un secret est frais s'il a été reçu après `after_seq` et il y a moins de `max_age`.
+    pub fn is_fresh(&self, after_seq: u64, max_age: Duration, now: Instant) -> bool {
+        self.seq > after_seq
+            && self
+                .received_at
+                .is_some_and(|received_at| now.saturating_duration_since(received_at) <= max_age)
+    }
+}
+
+/// Secrets des joueurs de l'équipe, avec l'ensemble des joueurs inscrits dont un secret
+/// est attendu.
+#[derive(Debug, Default)]
+pub struct SecretStore {
+    values: HashMap<String, SecretValue>,
+    subscribed: BTreeSet<String>,
+    last_seq: u64,
+}
+
+impl SecretStore {
+    /// Secrets repris d'une sauvegarde : ils comptent dans la somme mais ne sont jamais frais.
+    pub fn from_values(values: HashMap<String, u64>) -> Self {
+        let mut store = Self::default();
+        for (player_name, value) in values {
+            store.last_seq += 1;
+            store.values.insert(
+                player_name,
+                SecretValue {
+                    value,
+                    seq: store.last_seq,
+                    received_at: None,
+                },
+            );
+        }
+        store
+    }
+
+    /// Enregistre le dernier secret reçu par `player_name` et retourne son numéro.
+    pub fn insert(&mut self, player_name: String, value: u64) -> u64 {
+        self.last_seq += 1;
+        self.values.insert(
+            player_name,
+            SecretValue {
+                value,
+                seq: self.last_seq,
+                received_at: Some(Instant::now()),
+            },
+        );
+        self.last_seq
+    }
+
+    pub fn get(&self, player_name: &str) -> Option<SecretValue> {
+        self.values.get(player_name).copied()
+    }
+
+    /// Valeurs actuelles, pour la sauvegarde.
+    pub fn values(&self) -> HashMap<String, u64> {
+        self.values
+            .iter()
+            .map(|(player_name, secret)| (player_name.clone(), secret.value))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Numéro du dernier secret reçu.
+    pub fn last_seq(&self) -> u64 {
+        self.last_seq
+    }
+
+    pub fn subscribe(&mut self, player_name: &str) {
+        self.subscribed.insert(player_name.to_string());
+    }
+
+    pub fn unsubscribe(&mut self, player_name: &str) {
+        self.subscribed.remove(player_name);
+    }
+
+    /// Joueurs inscrits sans secret frais (voir [`SecretValue::is_fresh`]), hormis
+    /// `waiting_player` : bloqué dans l'attente, il ne peut pas recevoir de nouveau secret.
+    pub fn missing(
+        &self,
+        after_seq: u64,
+        max_age: Duration,
+        now: Instant,
+        waiting_player: &str,
+    ) -> Vec<String> {
+        self.subscribed
+            .iter()
+            .filter(|player_name| {
+                player_name.as_str() != waiting_player
+                    && !self
+                        .get(player_name)
+                        .is_some_and(|secret| secret.is_fresh(after_seq, max_age, now))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Photographie des secrets, en signalant les joueurs dont le secret manque ou a vieilli.
+    pub fn snapshot(
+        &self,
+        after_seq: u64,
+        max_age: Duration,
+        now: Instant,
+        waiting_player: &str,
+    ) -> SecretSnapshot {
+        let mut values: Vec<(String, u64)> = self.values().into_iter().collect();
+        values.sort();
+        SecretSnapshot {
+            values,
+            seq: self.last_seq,
+            missing: self.missing(after_seq, max_age, now, waiting_player),
+        }
+    }
+}
+
+/// Secrets utilisés pour répondre à un challenge `SecretSumModulo`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretSnapshot {
+    /// Secrets de chaque joueur, triés par nom
+    pub values: Vec<(String, u64)>,
+    /// Numéro du dernier secret pris en compte
+    pub seq: u64,
+    /// Joueurs inscrits dont le secret manquait ou n'était plus frais
+    pub missing: Vec<String>,
+}
+
+impl SecretSnapshot {
+    /// Somme des secrets modulo `modulo`, calculée sans dépassement.
+    pub fn sum_modulo(&self, modulo: u64) -> Result<u64, String> {
+        if modulo == 0 {
+            return Err("Modulo nul : aucune réponse possible".to_string());
+        }
+        let sum: u128 = self.values.iter().map(|&(_, value)| value as u128).sum();
+        Ok((sum % modulo as u128) as u64)
+    }
+}
@@ -0,0 +1,176 @@
+use crate::map::{GridSize, MazeMap};
+use crate::player::{Orientation, Position};
+use crate::radar::RadarView;
+use crate::triangulation::GoalEstimate;
+use std::collections::{HashMap, HashSet};
+
+/// Passage du repère d'un joueur à celui d'une carte : `carte = joueur + offset`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Frame {
+    offset: Position,
+}
+
+impl Frame {
+    pub fn new(offset: Position) -> Self {
+        Self { offset }
+    }
+
+    /// Repère confondu avec celui du joueur.
+    pub fn identity() -> Self {
+        Self::new(Position::new(0, 0))
+    }
+
+    pub fn offset(self) -> Position {
+        self.offset
+    }
+
+    pub fn to_map(self, position: Position) -> Position {
+        position + self.offset
+    }
+
+    pub fn to_player(self, position: Position) -> Position {
+        position - self.offset
+    }
+
+    pub fn cells_to_map(self, cells: &HashSet<Position>) -> HashSet<Position> {
+        cells.iter().map(|&cell| self.to_map(cell)).collect()
+    }
+
+    pub fn path_to_player(self, path: Vec<Position>) -> Vec<Position> {
+        path.into_iter().map(|cell| self.to_player(cell)).collect()
+    }
+
+    pub fn estimate_to_player(self, estimate: GoalEstimate) -> GoalEstimate {
+        estimate.translated(Position::new(0, 0) - self.offset)
+    }
+}
+
+/// Cartes du labyrinthe de l'équipe.
+///
+/// Par défaut, tous les joueurs sont supposés démarrer sur la même cellule, orientés au
+/// nord : ils partagent dès le départ la carte commune, exprimée dans leur repère.
+///
+/// Avec `separate_spawns`, chacun construit d'abord sa propre carte, dans son repère. Dès que
+/// ses vues couvrent toute la largeur et toute la hauteur du labyrinthe (indice `GridSize`),
+/// l'origine de la grille réelle y est connue ; sa carte est alors fusionnée dans la carte
+/// commune, exprimée dans le repère de la grille, qu'il partage ensuite avec les autres
+/// joueurs alignés. Seul le décalage entre les repères est inconnu : l'orientation de départ
+/// reste supposée la même pour tous.
+#[derive(Debug, Default)]
+pub struct TeamMap {
+    separate_spawns: bool,
+    /// Carte des joueurs alignés
+    shared: MazeMap,
+    /// Carte de chaque joueur pas encore aligné, dans son repère
+    local: HashMap<String, MazeMap>,
+    /// Repère de la carte commune, pour chaque joueur aligné
+    frames: HashMap<String, Frame>,
+}
+
+impl TeamMap {
+    pub fn new(separate_spawns: bool) -> Self {
+        Self {
+            separate_spawns,
+            ..Default::default()
+        }
+    }
+
+    /// Repère de la carte commune pour `player_name`, s'il y est aligné.
+    pub fn frame(&self, player_name: &str) -> Option<Frame> {
+        if !self.separate_spawns {
+            return Some(Frame::identity());
+        }
+        self.frames.get(player_name).copied()
+    }
+
+    /// Carte utilisée par `player_name` et son repère : la carte commune s'il y est aligné,
+    /// sinon sa propre carte.
+    pub fn view(&self, player_name: &str) -> Option<(&MazeMap, Frame)> {
+        match self.frame(player_name) {
+            Some(frame) => Some((&self.shared, frame)),
+            None => self
+                .local
+                .get(player_name)
+                .map(|map| (map, Frame::identity())),
+        }
+    }
+
+    pub fn shared(&self) -> &MazeMap {
+        &self.shared
+    }
+
+    /// Intègre la vue radar de `player_name`, situé en `position` dans son repère.
+    ///
+    /// Retourne la position de la sortie, dans le repère du joueur, si cette vue vient de
+    /// la révéler.
+    pub fn integrate(
+        &mut self,
+        player_name: &str,
+        radar: &RadarView,
+        position: Position,
+        orientation: Orientation,
+    ) -> Option<Position> {
+        if let Some(frame) = self.frame(player_name) {
+            let known_goal = self.shared.goal();
+            self.shared
+                .integrate(radar, frame.to_map(position), orientation);
+            return self
+                .shared
+                .goal()
+                .filter(|_| known_goal.is_none())
+                .map(|goal| frame.to_player(goal));
+        }
+
+        let grid_size = self.shared.grid_size();
+        let map = self
+            .local
+            .entry(player_name.to_string())
+            .or_insert_with(|| {
+                let mut map = MazeMap::default();
+                if let Some(grid_size) = grid_size {
+                    map.set_grid_size(grid_size);
+                }
+                map
+            });
+        let known_goal = map.goal();
+        map.integrate(radar, position, orientation);
+        map.goal().filter(|_| known_goal.is_none())
+    }
+
+    /// Enregistre les dimensions du labyrinthe. Retourne `true` si elles étaient inconnues
+    /// ou différentes.
+    pub fn set_grid_size(&mut self, grid_size: GridSize) -> bool {
+        for map in self.local.values_mut() {
+            map.set_grid_size(grid_size);
+        }
+        self.shared.set_grid_size(grid_size)
+    }
+
+    /// Aligne sur la carte commune les joueurs dont la carte situe l'origine de la grille,
+    /// et y fusionne leur carte. Retourne les joueurs nouvellement alignés et leur repère.
+    pub fn align(&mut self) -> Vec<(String, Frame)> {
+        let origins: Vec<(String, Position)> = self
+            .local
+            .iter()
+            .filter_map(|(name, map)| Some((name.clone(), map.grid_origin()?)))
+            .collect();
+
+        let mut aligned = Vec::new();
+        for (name, origin) in origins {
+            let Some(map) = self.local.remove(&name) else {
+                continue;
+            };
+            let frame = Frame::new(Position::new(0, 0) - origin);
+            self.shared.merge(&map, frame.offset());
+            self.frames.insert(name.clone(), frame);
+            aligned.push((name, frame));
+        }
+        aligned
+    }
+
+    /// La carte de `player_name` a été explorée sans que personne n'en voie la sortie.
+    pub fn is_exhausted(&self, player_name: &str) -> bool {
+        self.view(player_name)
+            .is_some_and(|(map, _)| map.is_exhausted())
+    }
+}
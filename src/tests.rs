@@ -1,59 +1,723 @@
 #[cfg(test)]
 mod tests {
-    use super::*;
-    use std::collections::HashMap;
-    use std::net::TcpStream;
-    use std::sync::{Arc, Mutex};
-    use crate::game::GameState;
-    use crate::player::{send_move_action, tremaux_decide_move, Orientation, PlayerState, Position};
-    use crate::utils::decode_b64;
+    use crate::challenge::{ChallengeContext, ChallengeSolvers};
+    use crate::checkpoint::Checkpoint;
+    use crate::client::connect_and_subscribe;
+    use crate::codec::{
+        FrameError, FrameReader, FrameWriter, FramedStream, DEFAULT_MAX_FRAME_SIZE,
+    };
+    use crate::config::{Config, ConfigError, RetryPolicy, SecretWaitPolicy};
+    use crate::coordinator::Coordinator;
+    use crate::direction::{absolute_bearing, bearing_vector, CardinalDirection};
+    use crate::game::{start_game_loop, GameLoopExit, GameState};
+    use crate::map::{Edge, GridSize, MazeMap};
+    use crate::navigation::random::random_decide_move;
+    use crate::navigation::{
+        ActionResult, Compass, ExitRouting, NavigationContext, NavigationStrategy, Pledge,
+        RescueRouting, StrategyRegistry, Tremaux, WallFollower,
+    };
+    use crate::planner::{a_star, bfs, shortest_path, to_relative_moves};
+    use crate::player::{
+        handle_secret_sum_modulo, move_player, send_move_action, subscribe_player, MovementLog,
+        Orientation, PlayerState, Position, SubscribeError,
+    };
+    use crate::protocol::{
+        parse_server_message, Action, ActionError, Challenge, ClientMessage, Hint,
+        RegisterTeamResult, RegistrationError, RelativeDirection, ServerMessage,
+        SubscribePlayerResult,
+    };
+    use crate::radar::{
+        Cell, CellEntity, CellNature, Passage, RadarError, RadarView, RADAR_VIEW_BYTES,
+    };
+    use crate::secrets::SecretStore;
+    use crate::triangulation::{CompassReading, Triangulation};
+    use crate::utils::{decode_b64, encode_b64, B64Error};
+    use std::collections::HashSet;
+    use std::io::Cursor;
+    use std::net::{TcpListener, TcpStream};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    /// Démarre un serveur local éphémère et renvoie son adresse.
+    fn local_server() -> (TcpListener, String) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Échec du bind local");
+        let address = listener
+            .local_addr()
+            .expect("Adresse locale indisponible")
+            .to_string();
+        (listener, address)
+    }
+
+    /// Vue radar sans aucun mur, toutes les cellules vides.
+    fn open_radar() -> RadarView {
+        RadarView {
+            horizontal: [Passage::Open; 12],
+            vertical: [Passage::Open; 12],
+            cells: [Cell::EMPTY; 9],
+        }
+    }
+
+    /// Demande un déplacement à `strategy` pour un joueur seul dans sa partie.
+    fn decide(
+        strategy: &mut dyn NavigationStrategy,
+        player_state: &mut PlayerState,
+        radar: &RadarView,
+    ) -> Option<RelativeDirection> {
+        let game_state = GameState::default();
+        let mut context = NavigationContext {
+            player_name: "player_test",
+            player_state,
+            game_state: &game_state,
+        };
+        strategy.decide(radar, &mut context)
+    }
+
+    /// Vue radar complète d'un joueur dans le labyrinthe 3x3 (coins (0, 0) à (2, 2)) dont
+    /// `open` liste les passages ouverts ; l'extérieur de la grille est inconnu.
+    fn maze_radar(open: &HashSet<Edge>, goal: Position, player_state: &PlayerState) -> RadarView {
+        let inside =
+            |position: Position| (0..3).contains(&position.x()) && (0..3).contains(&position.y());
+        let layout = open_radar().to_world(player_state.position, player_state.orientation);
+
+        let mut radar = open_radar();
+        for (i, (position, _)) in layout.cells.iter().enumerate() {
+            radar.cells[i] = if !inside(*position) {
+                Cell::Undefined
+            } else if *position == goal {
+                Cell::Known {
+                    nature: CellNature::Goal,
+                    entity: CellEntity::None,
+                }
+            } else {
+                Cell::EMPTY
+            };
+        }
+        for (i, (edge, _)) in layout.passages.iter().enumerate() {
+            let (a, b) = edge.cells();
+            let passage = if open.contains(edge) {
+                Passage::Open
+            } else if inside(a) || inside(b) {
+                Passage::Wall
+            } else {
+                Passage::Undefined
+            };
+            if i < 12 {
+                radar.horizontal[i] = passage;
+            } else {
+                radar.vertical[i - 12] = passage;
+            }
+        }
+        radar
+    }
+
+    /// Labyrinthe parfait 3x3 : départ en (0, 0) face à un mur, sortie en (2, 1).
+    ///
+    /// ```text
+    /// S - . - .
+    /// |
+    /// .   . - G
+    /// |   |   |
+    /// . - . - .
+    /// ```
+    fn perfect_maze() -> HashSet<Edge> {
+        [
+            ((0, 0), Orientation::East),
+            ((1, 0), Orientation::East),
+            ((0, 0), Orientation::South),
+            ((0, 1), Orientation::South),
+            ((0, 2), Orientation::East),
+            ((1, 2), Orientation::North),
+            ((1, 2), Orientation::East),
+            ((2, 2), Orientation::North),
+        ]
+        .into_iter()
+        .map(|((x, y), side)| Edge::new(Position::new(x, y), side))
+        .collect()
+    }
+
+    /// Carte obtenue après avoir vu chaque cellule 3x3 de `open` avec un radar réduit.
+    fn known_map(open: &HashSet<Edge>, goal: Position) -> MazeMap {
+        let mut map = MazeMap::default();
+        for x in 0..3 {
+            for y in 0..3 {
+                let player_state = PlayerState::new(Position::new(x, y), Orientation::North);
+                let radar = maze_radar(open, goal, &player_state);
+                map.integrate(&radar, player_state.position, player_state.orientation);
+            }
+        }
+        map
+    }
+
+    /// Fait jouer `strategy` seule dans le labyrinthe `open` depuis (0, 0) orienté au nord,
+    /// chaque déplacement étant accepté. Renvoie le nombre de pas pour atteindre `goal`,
+    /// ou `None` si la stratégie s'arrête ou dépasse `max_steps`.
+    fn walk(
+        strategy: &mut dyn NavigationStrategy,
+        open: &HashSet<Edge>,
+        goal: Position,
+        max_steps: usize,
+    ) -> Option<usize> {
+        let game_state = GameState::default();
+        let mut player_state = PlayerState::new(Position::new(0, 0), Orientation::North);
+        for steps in 0..=max_steps {
+            if player_state.position == goal {
+                return Some(steps);
+            }
+            let radar = maze_radar(open, goal, &player_state);
+            game_state.integrate_radar("player_test", &radar, &player_state);
+            strategy.observe_radar(&radar, &player_state);
+            let mut context = NavigationContext {
+                player_name: "player_test",
+                player_state: &mut player_state,
+                game_state: &game_state,
+            };
+            let direction = strategy.decide(&radar, &mut context)?;
+
+            let side = player_state.orientation.rotate(direction);
+            assert!(
+                open.contains(&Edge::new(player_state.position, side)),
+                "Passage fermé emprunté"
+            );
+            move_player(&mut player_state, direction);
+            player_state.pending_move = None;
+            strategy.observe_action_result(ActionResult::Confirmed(direction));
+        }
+        None
+    }
+
+    /// Stratégie de test : tourne toujours à gauche et compte ce qu'elle observe.
+    #[derive(Default)]
+    struct TurnLeft {
+        radars: usize,
+        results: Vec<ActionResult>,
+    }
+
+    impl NavigationStrategy for TurnLeft {
+        fn observe_radar(&mut self, _radar: &RadarView, _player_state: &PlayerState) {
+            self.radars += 1;
+        }
+
+        fn observe_action_result(&mut self, result: ActionResult) {
+            self.results.push(result);
+        }
+
+        fn decide(
+            &mut self,
+            _radar: &RadarView,
+            _context: &mut NavigationContext,
+        ) -> Option<RelativeDirection> {
+            Some(RelativeDirection::Left)
+        }
+    }
+
+    /// Configuration minimale pointant vers `server_address`, avec des attentes courtes.
+    fn test_config(server_address: &str) -> Config {
+        toml::from_str(&format!(
+            r#"
+            server_address = "{}"
+            team_name = "team_test"
+            navigation_mode = "tremaux"
+            save_progress = false
+
+            [subscribe_retry]
+            max_attempts = 2
+            initial_backoff_ms = 1
+            "#,
+            server_address
+        ))
+        .unwrap()
+    }
 
     #[test]
     fn test_compute_new_position() {
-        let player = PlayerState {
-            position: Position::new(0, 0),
-            visited: HashMap::new(),
-            last_direction: None,
-            orientation: Orientation::North,
-        };
+        let player = PlayerState::new(Position::new(0, 0), Orientation::North);
 
-        let new_position = player.compute_new_position("Front");
+        let new_position = player.compute_new_position(RelativeDirection::Front);
         assert_eq!(new_position, Position::new(0, -1));
     }
 
     #[test]
     fn test_update_orientation() {
-        let mut player = PlayerState {
-            position: Position::new(0, 0),
-            visited: HashMap::new(),
-            last_direction: None,
-            orientation: Orientation::North,
-        };
+        let mut player = PlayerState::new(Position::new(0, 0), Orientation::North);
 
-        player.update_orientation("Left");
+        player.update_orientation(RelativeDirection::Left);
         assert_eq!(player.orientation, Orientation::West);
     }
 
+    #[test]
+    fn test_direction_algebra() {
+        use RelativeDirection::{Back, Front, Left, Right};
+
+        assert_eq!(Right.compose(Right), Back);
+        assert_eq!(Left.compose(Right), Front);
+        assert_eq!(Back.compose(Left), Right);
+        assert_eq!(Left.invert(), Right);
+        assert_eq!(Front.invert(), Back);
+
+        assert_eq!(
+            CardinalDirection::North.rotate(Right),
+            CardinalDirection::East
+        );
+        assert_eq!(
+            CardinalDirection::West.rotate(Right),
+            CardinalDirection::North
+        );
+        assert_eq!(
+            CardinalDirection::South.rotate(Left),
+            CardinalDirection::East
+        );
+        assert_eq!(CardinalDirection::East.opposite(), CardinalDirection::West);
+        for facing in CardinalDirection::ALL {
+            for turn in RelativeDirection::ALL {
+                assert_eq!(facing.rotate(turn).relative_to(facing), turn);
+                assert_eq!(facing.rotate(turn.invert()), facing.rotate(turn).opposite());
+            }
+        }
+
+        assert_eq!(
+            Position::new(2, 3) + CardinalDirection::North,
+            Position::new(2, 2)
+        );
+        assert_eq!(
+            Position::new(2, 3) + CardinalDirection::West,
+            Position::new(1, 3)
+        );
+        assert_eq!(serde_json::to_string(&Left).unwrap(), r#""Left""#);
+        assert_eq!("Back".parse::<RelativeDirection>(), Ok(Back));
+        assert!("Up".parse::<RelativeDirection>().is_err());
+    }
+
+    #[test]
+    fn test_move_and_orientation_agree() {
+        // Le joueur avance dans la direction prise et lui fait face ensuite.
+        let mut player = PlayerState::new(Position::new(0, 0), Orientation::East);
+        move_player(&mut player, RelativeDirection::Left);
+        assert_eq!(player.position, Position::new(0, -1));
+        assert_eq!(player.orientation, Orientation::North);
+        move_player(&mut player, RelativeDirection::Back);
+        assert_eq!(player.position, Position::new(0, 0));
+        assert_eq!(player.orientation, Orientation::South);
+    }
+
+    #[test]
+    fn test_rollback_rejected_move() {
+        let mut player = PlayerState::new(Position::new(0, 0), Orientation::North);
+        player.visited.insert(Position::new(0, 0), 1);
+
+        move_player(&mut player, RelativeDirection::Right);
+        assert_eq!(player.position, Position::new(1, 0));
+        assert_eq!(player.orientation, Orientation::East);
+        assert_eq!(player.visited.get(&Position::new(1, 0)), Some(&1));
+
+        let rejected = player
+            .rollback_move()
+            .expect("Un déplacement devait être en attente");
+        assert_eq!(rejected.movement, RelativeDirection::Right);
+        assert_eq!(player.position, Position::new(0, 0));
+        assert_eq!(player.orientation, Orientation::North);
+        assert_eq!(player.visited.get(&Position::new(1, 0)), None);
+        assert_eq!(player.visited.get(&Position::new(0, 0)), Some(&1));
+        assert!(player.rollback_move().is_none());
+    }
+
+    #[test]
+    fn test_snapshot_restore_player_state() {
+        let mut player = PlayerState::new(Position::new(0, 0), Orientation::North);
+        move_player(&mut player, RelativeDirection::Front);
+        player.pending_move = None;
+        player.last_direction = Some(RelativeDirection::Left);
+        move_player(&mut player, RelativeDirection::Left);
+
+        // Le déplacement non confirmé n'est pas sauvegardé.
+        let saved = player.snapshot();
+        assert_eq!(saved.position, Position::new(0, -1));
+        assert_eq!(saved.orientation, Orientation::North);
+        assert_eq!(saved.visited, vec![(Position::new(0, -1), 1)]);
+
+        let restored = PlayerState::restore(&saved);
+        assert_eq!(restored.position, Position::new(0, -1));
+        assert_eq!(restored.last_direction, Some(RelativeDirection::Left));
+        assert_eq!(restored.visited.get(&Position::new(0, -1)), Some(&1));
+        assert!(restored.pending_move.is_none());
+    }
+
+    #[test]
+    fn test_checkpoint_save_and_resume() {
+        let path = std::env::temp_dir().join(format!("sauvequipeut_{}.json", std::process::id()));
+        let game_state = GameState::default();
+        game_state
+            .secrets
+            .lock()
+            .unwrap()
+            .insert("player_2".to_string(), 99);
+
+        let player = PlayerState::new(Position::new(3, 4), Orientation::West);
+        game_state
+            .save_checkpoint("player_1", &player, &path)
+            .unwrap();
+
+        let checkpoint = Checkpoint::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(checkpoint.secrets.get("player_2"), Some(&99));
+
+        let resumed = GameState::from_checkpoint(checkpoint, false);
+        let saved = resumed.saved_player("player_1").unwrap();
+        assert_eq!(saved.position, Position::new(3, 4));
+        assert_eq!(saved.orientation, Orientation::West);
+        assert!(resumed.saved_player("player_2").is_none());
+    }
+
+    #[test]
+    fn test_decide_move_respects_walls() {
+        // Cellules toutes vides : seuls les murs distinguent les directions.
+        let mut radar = open_radar();
+        radar.horizontal[4] = Passage::Wall; // devant
+        radar.vertical[5] = Passage::Wall; // à gauche
+        radar.vertical[6] = Passage::Undefined; // à droite
+        assert!(!radar.is_reachable(RelativeDirection::Front));
+        assert!(!radar.is_reachable(RelativeDirection::Left));
+        assert!(!radar.is_reachable(RelativeDirection::Right));
+        assert!(radar.is_reachable(RelativeDirection::Back));
+
+        let mut player_state = PlayerState::new(Position::new(0, 0), Orientation::North);
+        let mut tremaux = Tremaux::default();
+        for _ in 0..10 {
+            assert_eq!(
+                decide(&mut tremaux, &mut player_state, &radar),
+                Some(RelativeDirection::Back)
+            );
+            assert_eq!(
+                random_decide_move(&radar, "player_test"),
+                Some(RelativeDirection::Back)
+            );
+        }
+
+        // Une sortie derrière un mur n'est pas atteignable.
+        radar.cells[1] = Cell::Known {
+            nature: CellNature::Goal,
+            entity: CellEntity::None,
+        };
+        assert_eq!(
+            decide(&mut tremaux, &mut player_state, &radar),
+            Some(RelativeDirection::Back)
+        );
+    }
+
+    #[test]
+    fn test_strategies_wait_when_no_move_is_legal() {
+        // Un mur devant et à gauche, un allié à droite, une cellule masquée derrière.
+        let mut radar = open_radar();
+        radar.horizontal[4] = Passage::Wall;
+        radar.vertical[5] = Passage::Wall;
+        radar.cells[5] = Cell::Known {
+            nature: CellNature::None,
+            entity: CellEntity::Ally,
+        };
+        radar.mask(RelativeDirection::Back);
+
+        let mut strategies: Vec<Box<dyn NavigationStrategy>> = vec![
+            Box::<Tremaux>::default(),
+            Box::new(WallFollower::left_hand()),
+            Box::new(WallFollower::right_hand()),
+            Box::<Pledge>::default(),
+        ];
+        for strategy in &mut strategies {
+            let mut player_state = PlayerState::new(Position::new(0, 0), Orientation::North);
+            assert_eq!(decide(strategy.as_mut(), &mut player_state, &radar), None);
+            assert!(!strategy.finished());
+        }
+        assert_eq!(random_decide_move(&radar, "player_test"), None);
+
+        // Une fois l'allié parti, la seule issue est à droite.
+        let vacated = radar.without_allies();
+        assert!(vacated.neighbour(RelativeDirection::Right).is_free());
+        for strategy in &mut strategies {
+            let mut player_state = PlayerState::new(Position::new(0, 0), Orientation::North);
+            assert_eq!(
+                decide(strategy.as_mut(), &mut player_state, &vacated),
+                Some(RelativeDirection::Right)
+            );
+        }
+    }
+
+    #[test]
+    fn test_maze_map_integrates_rotated_radar() {
+        let mut radar = open_radar();
+        radar.cells[1] = Cell::Known {
+            nature: CellNature::Goal,
+            entity: CellEntity::None,
+        };
+        radar.cells[3] = Cell::Known {
+            nature: CellNature::Hint,
+            entity: CellEntity::None,
+        };
+        radar.vertical[6] = Passage::Wall; // à droite du joueur
+
+        // Orienté à l'est : devant = est, droite = sud, gauche = nord.
+        let mut map = MazeMap::default();
+        map.integrate(&radar, Position::new(2, 3), Orientation::East);
+
+        assert_eq!(map.goal(), Some(Position::new(3, 3)));
+        assert_eq!(map.hints().collect::<Vec<_>>(), vec![Position::new(2, 2)]);
+        assert_eq!(map.known_cells(), 9);
+        assert!(map.is_open(Position::new(2, 3), Orientation::East));
+        assert!(map.is_open(Position::new(3, 3), Orientation::West));
+        assert_eq!(
+            map.passage(Position::new(2, 3), Orientation::South),
+            Passage::Wall
+        );
+        assert_eq!(
+            map.passage(Position::new(2, 4), Orientation::North),
+            Passage::Wall
+        );
+        assert_eq!(
+            map.passage(Position::new(9, 9), Orientation::North),
+            Passage::Undefined
+        );
+
+        // Une vue vide n'efface rien de ce qui est connu.
+        let blind = RadarView {
+            horizontal: [Passage::Undefined; 12],
+            vertical: [Passage::Undefined; 12],
+            cells: [Cell::Undefined; 9],
+        };
+        map.integrate(&blind, Position::new(2, 3), Orientation::East);
+        assert_eq!(map.known_cells(), 9);
+        assert_eq!(
+            map.passage(Position::new(2, 4), Orientation::North),
+            Passage::Wall
+        );
+    }
+
+    #[test]
+    fn test_radar_to_world_all_orientations() {
+        let goal = Cell::Known {
+            nature: CellNature::Goal,
+            entity: CellEntity::None,
+        };
+        let hint = Cell::Known {
+            nature: CellNature::Hint,
+            entity: CellEntity::None,
+        };
+        let mut radar = open_radar();
+        radar.cells[0] = goal; // devant à gauche
+        radar.cells[5] = hint; // à droite
+        radar.horizontal[4] = Passage::Wall; // devant le joueur
+        radar.vertical[5] = Passage::Wall; // à gauche du joueur
+
+        let center = Position::new(5, 5);
+        // (orientation, devant-gauche, droite, devant, gauche, côté droit)
+        let cases = [
+            (
+                Orientation::North,
+                (4, 4),
+                (6, 5),
+                Orientation::North,
+                Orientation::West,
+                Orientation::East,
+            ),
+            (
+                Orientation::East,
+                (6, 4),
+                (5, 6),
+                Orientation::East,
+                Orientation::North,
+                Orientation::South,
+            ),
+            (
+                Orientation::South,
+                (6, 6),
+                (4, 5),
+                Orientation::South,
+                Orientation::East,
+                Orientation::West,
+            ),
+            (
+                Orientation::West,
+                (4, 6),
+                (5, 4),
+                Orientation::West,
+                Orientation::South,
+                Orientation::North,
+            ),
+        ];
+
+        for (orientation, front_left, right, front, left, right_side) in cases {
+            let view = radar.to_world(center, orientation);
+            assert_eq!(view.cell(center), Some(Cell::EMPTY));
+            assert_eq!(
+                view.cell(Position::new(front_left.0, front_left.1)),
+                Some(goal)
+            );
+            assert_eq!(view.cell(Position::new(right.0, right.1)), Some(hint));
+            assert_eq!(view.cell(Position::new(9, 9)), None);
+
+            assert_eq!(view.passage(center, front), Some(Passage::Wall));
+            assert_eq!(view.passage(center, left), Some(Passage::Wall));
+            assert_eq!(
+                view.passage(center.neighbour(front), front),
+                Some(Passage::Open)
+            );
+            // Passages du bord de la vue : la ligne 3 et la colonne 3 sont à deux cases du joueur.
+            let far_corner = center.neighbour(front.opposite()).neighbour(right_side);
+            assert_eq!(view.passage(far_corner, right_side), Some(Passage::Open));
+            assert_eq!(
+                view.passage(far_corner, front.opposite()),
+                Some(Passage::Open)
+            );
+            // Le même passage vu depuis la cellule voisine.
+            assert_eq!(
+                view.passage(center.neighbour(left), right_side),
+                Some(Passage::Wall)
+            );
+        }
+    }
+
+    #[test]
+    fn test_mask_direction() {
+        let mut radar = open_radar();
+        radar.mask(RelativeDirection::Left);
+        radar.mask(RelativeDirection::Front);
+        assert_eq!(radar.neighbour(RelativeDirection::Left), Cell::Undefined);
+        assert_eq!(radar.neighbour(RelativeDirection::Front), Cell::Undefined);
+        assert_eq!(radar.neighbour(RelativeDirection::Right), Cell::EMPTY);
+        assert!(ActionError::CannotPassThroughWall.rejects_move());
+        assert!(!ActionError::InvalidChallengeSolution.rejects_move());
+    }
+
     #[test]
     fn test_handle_secret_sum_modulo() {
-        let game_state = Arc::new(GameState {
-            secrets: Mutex::new(HashMap::new()),
+        let game_state = Arc::new(GameState::default());
+        game_state.register_player("player_1");
+        game_state.register_player("player_3");
+        let policy = SecretWaitPolicy {
+            timeout_ms: 2_000,
+            max_age_ms: 60_000,
+        };
+        let answer = |writer: &FrameWriter<Vec<u8>>| {
+            let mut reader = FrameReader::new(
+                Cursor::new(writer.get_ref().clone()),
+                DEFAULT_MAX_FRAME_SIZE,
+            );
+            let mut last = None;
+            while let Ok(message) = reader.recv::<ClientMessage>() {
+                last = Some(message);
+            }
+            last
+        };
+        let solve = |answer: &str| {
+            Some(ClientMessage::Action(Action::SolveChallenge {
+                answer: answer.to_string(),
+            }))
+        };
+
+        // Le secret de player_3 arrive pendant l'attente.
+        game_state.record_secret("player_1", 11);
+        let late = thread::spawn({
+            let game_state = game_state.clone();
+            move || {
+                thread::sleep(Duration::from_millis(20));
+                game_state.record_secret("player_3", 32);
+            }
         });
+        let mut writer = FrameWriter::new(Vec::new(), DEFAULT_MAX_FRAME_SIZE);
+        let seq = handle_secret_sum_modulo(&mut writer, "player_1", &game_state, &policy, 10, 0);
+        late.join().unwrap();
+        assert_eq!(seq, Some(2));
+        assert_eq!(answer(&writer), solve("3"));
 
-        {
-            let mut secrets = game_state.secrets.lock().unwrap();
-            secrets.insert("player_1".to_string(), 11);
-            secrets.insert("player_3".to_string(), 32);
-        }
+        // Réponse refusée : player_1, bloqué dans l'attente, n'attend pas son propre secret.
+        game_state.record_secret("player_3", 33);
+        let started = std::time::Instant::now();
+        let snapshot = game_state.wait_for_secrets(&policy, 2, "player_1").unwrap();
+        assert!(snapshot.missing.is_empty());
+        assert!(started.elapsed() < policy.timeout());
+        let seq = handle_secret_sum_modulo(&mut writer, "player_1", &game_state, &policy, 10, 2);
+        assert_eq!(seq, Some(3));
+        assert_eq!(answer(&writer), solve("4"));
 
-        let modulo = 10;
-        let result = {
-            let secrets = game_state.secrets.lock().unwrap();
-            let sum: u128 = secrets.values().map(|&v| v as u128).sum();
-            (sum % modulo as u128) as u64
+        // Sans nouveau secret d'un coéquipier, l'attente s'arrête au délai maximal.
+        let policy = SecretWaitPolicy {
+            timeout_ms: 50,
+            ..policy
         };
+        let snapshot = game_state.wait_for_secrets(&policy, 3, "player_1").unwrap();
+        assert_eq!(snapshot.missing, vec!["player_3".to_string()]);
+
+        // Modulo nul : erreur, aucune réponse envoyée.
+        assert!(snapshot.sum_modulo(0).is_err());
+        assert_eq!(
+            handle_secret_sum_modulo(&mut writer, "player_1", &game_state, &policy, 0, 3),
+            None
+        );
+        assert_eq!(answer(&writer), solve("4"));
+
+        // Les secrets repris d'une sauvegarde comptent mais ne sont jamais frais.
+        let store = SecretStore::from_values([("player_2".to_string(), 5)].into_iter().collect());
+        let secret = store.get("player_2").unwrap();
+        assert!(!secret.is_fresh(0, Duration::MAX, std::time::Instant::now()));
+    }
+
+    #[test]
+    fn test_decode_radar_view() {
+        let radar = RadarView::decode("zveKvsuL8a8aaaa").unwrap();
+
+        use Passage::{Open as O, Undefined as U, Wall as W};
+        assert_eq!(radar.horizontal, [U, W, O, U, O, O, U, O, O, W, O, O]);
+        assert_eq!(radar.vertical, [U, W, O, O, U, W, O, O, O, O, O, O]);
+        assert_eq!(radar.cell(0, 0), Cell::Undefined);
+        assert_eq!(radar.cell(1, 0), Cell::Undefined);
+        assert_eq!(radar.cell(1, 1), Cell::EMPTY);
+        assert_eq!(radar.horizontal_passage(3, 0), Passage::Wall);
+        assert_eq!(radar.vertical_passage(0, 1), Passage::Wall);
+    }
+
+    #[test]
+    fn test_decode_radar_cells() {
+        let mut bytes = [0b0101_0101; RADAR_VIEW_BYTES];
+        bytes[6..11].copy_from_slice(&[0x01, 0x23, 0x48, 0x9B, 0xF0]);
+        let radar = RadarView::from_bytes(&bytes).unwrap();
+
+        let known = |nature, entity| Cell::Known { nature, entity };
+        assert_eq!(radar.cells[0], Cell::EMPTY);
+        assert_eq!(radar.cells[1], known(CellNature::None, CellEntity::Ally));
+        assert_eq!(radar.cells[2], known(CellNature::None, CellEntity::Enemy));
+        assert_eq!(radar.cells[3], known(CellNature::None, CellEntity::Monster));
+        assert_eq!(radar.cells[4], known(CellNature::Hint, CellEntity::None));
+        assert_eq!(radar.cells[5], known(CellNature::Goal, CellEntity::None));
+        assert_eq!(radar.cells[6], known(CellNature::Goal, CellEntity::Ally));
+        assert_eq!(radar.cells[7], known(CellNature::Goal, CellEntity::Monster));
+        assert_eq!(radar.cells[8], Cell::Undefined);
+        assert!(radar.cells[6].is_goal() && !radar.cells[6].is_free());
+    }
+
+    #[test]
+    fn test_decode_radar_view_errors() {
+        assert_eq!(
+            RadarView::decode("zveKvsuL8a8"),
+            Err(RadarError::InvalidLength {
+                expected: RADAR_VIEW_BYTES,
+                actual: 8
+            })
+        );
+        assert!(matches!(
+            RadarView::decode("zveKvsuL8a8aa!a"),
+            Err(RadarError::InvalidEncoding(_))
+        ));
 
-        assert_eq!(result, 3);
+        let mut bytes = [0; RADAR_VIEW_BYTES];
+        bytes[0] = 0b11;
+        assert_eq!(
+            RadarView::from_bytes(&bytes),
+            Err(RadarError::InvalidPassage(0b11))
+        );
+        let mut bytes = [0; RADAR_VIEW_BYTES];
+        bytes[6] = 0xC0;
+        assert_eq!(
+            RadarView::from_bytes(&bytes),
+            Err(RadarError::InvalidCell(0xC))
+        );
     }
 
     #[test]
@@ -61,46 +725,1234 @@ mod tests {
         let encoded = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789+/";
         let decoded = decode_b64(encoded);
         assert!(decoded.is_ok());
+        assert_eq!(encode_b64(&decoded.unwrap()), encoded);
+    }
+
+    #[test]
+    fn test_b64_round_trip() {
+        assert_eq!(encode_b64(&[]), "");
+        assert_eq!(encode_b64(&[0]), "aa");
+        assert_eq!(encode_b64(&[0xFF, 0xFF]), "//8");
+        assert_eq!(decode_b64("//8").unwrap(), vec![0xFF, 0xFF]);
+
+        let bytes: Vec<u8> = (0..=255).collect();
+        for length in 0..bytes.len() {
+            let encoded = encode_b64(&bytes[..length]);
+            assert_eq!(encoded.len(), (length * 4).div_ceil(3));
+            assert_eq!(decode_b64(&encoded).unwrap(), &bytes[..length]);
+        }
+    }
+
+    #[test]
+    fn test_b64_errors() {
+        assert_eq!(decode_b64("abcde"), Err(B64Error::InvalidLength(5)));
+        assert_eq!(
+            decode_b64("ab=d"),
+            Err(B64Error::InvalidCharacter {
+                character: '=',
+                position: 2
+            })
+        );
+        // "ab" : le second caractère porte 4 bits de remplissage non nuls.
+        assert_eq!(decode_b64("ab"), Err(B64Error::NonZeroTrailingBits));
+    }
+
+    #[test]
+    fn test_radar_view_encode_round_trip() {
+        let radar = RadarView::decode("zveKvsuL8a8aaaa").unwrap();
+        assert_eq!(radar.encode(), "zveKvsuL8a8aaaa");
+
+        let mut radar = open_radar();
+        radar.cells[1] = Cell::Known {
+            nature: CellNature::Goal,
+            entity: CellEntity::Enemy,
+        };
+        radar.cells[8] = Cell::Undefined;
+        radar.horizontal[11] = Passage::Wall;
+        radar.vertical[0] = Passage::Undefined;
+        assert_eq!(RadarView::decode(&radar.encode()).unwrap(), radar);
     }
 
     #[test]
     fn test_server_connection() {
-        let server_address = "127.0.0.1:8778";
+        let (_listener, server_address) = local_server();
         let connection = TcpStream::connect(server_address);
         assert!(connection.is_ok(), "La connexion au serveur a échoué !");
     }
 
     #[test]
     fn test_send_move_action() {
-        let server_address = "127.0.0.1:8778";
-        let mut stream = TcpStream::connect(server_address).expect("Échec de connexion");
+        let (listener, server_address) = local_server();
+        let stream = TcpStream::connect(server_address).expect("Échec de connexion");
+        let mut writer = FrameWriter::new(stream, DEFAULT_MAX_FRAME_SIZE);
 
-        let result = send_move_action(&mut stream, "Front", "player_test");
+        let result = send_move_action(&mut writer, RelativeDirection::Front, "player_test");
         assert!(result.is_ok(), "Échec d'envoi de l'action de déplacement !");
+
+        let (server_side, _) = listener.accept().expect("Aucun client accepté");
+        let mut reader = FrameReader::new(server_side, DEFAULT_MAX_FRAME_SIZE);
+        assert_eq!(
+            reader.read_frame().unwrap(),
+            br#"{"Action":{"MoveTo":"Front"}}"#
+        );
     }
 
     #[test]
-    fn test_tremaux_decide_move() {
-        let mut player_state = PlayerState {
-            position: Position::new(0, 0),
-            visited: HashMap::new(),
-            last_direction: None,
-            orientation: Orientation::North,
+    fn test_subscribe_player_rejected() {
+        let (listener, server_address) = local_server();
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut connection = FramedStream::new(stream, DEFAULT_MAX_FRAME_SIZE).unwrap();
+            let _: ClientMessage = connection.reader.recv().unwrap();
+            connection
+                .writer
+                .send(&ServerMessage::SubscribePlayerResult(
+                    SubscribePlayerResult::Err(RegistrationError::InvalidRegistrationToken),
+                ))
+                .unwrap();
+        });
+
+        let mut connection =
+            FramedStream::connect(&server_address, DEFAULT_MAX_FRAME_SIZE).unwrap();
+        let result = subscribe_player(&mut connection, "player_1", "bad_token");
+        server.join().unwrap();
+
+        match result {
+            Err(e @ SubscribeError::Rejected(RegistrationError::InvalidRegistrationToken)) => {
+                assert!(!e.is_retryable())
+            }
+            other => panic!("Refus attendu, obtenu {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_connect_and_subscribe_retries() {
+        let (listener, server_address) = local_server();
+        let server = thread::spawn(move || {
+            // Première connexion : réponse inattendue, seconde : inscription acceptée.
+            for response in [
+                ServerMessage::RadarView("zveKvsuL8a8aaaa".to_string()),
+                ServerMessage::SubscribePlayerResult(SubscribePlayerResult::Ok),
+            ] {
+                let (stream, _) = listener.accept().unwrap();
+                let mut connection = FramedStream::new(stream, DEFAULT_MAX_FRAME_SIZE).unwrap();
+                let _: ClientMessage = connection.reader.recv().unwrap();
+                connection.writer.send(&response).unwrap();
+            }
+        });
+
+        let config = test_config(&server_address);
+
+        let result = connect_and_subscribe(&config, "player_1", "abcd1234");
+        server.join().unwrap();
+        assert!(
+            result.is_ok(),
+            "La seconde tentative aurait dû réussir : {:?}",
+            result.err()
+        );
+    }
+
+    #[test]
+    fn test_game_loop_reports_disconnection() {
+        let (listener, server_address) = local_server();
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            drop(stream);
+        });
+
+        let config = test_config(&server_address);
+        let mut connection =
+            FramedStream::connect(&server_address, DEFAULT_MAX_FRAME_SIZE).unwrap();
+        server.join().unwrap();
+
+        let game_state = Arc::new(GameState::default());
+        let mut player_state = PlayerState::new(Position::new(2, -1), Orientation::East);
+        let logger = MovementLog {
+            player_name: "player_test".to_string(),
         };
 
-        let radar_data = vec![
-            vec!["01 01 01".to_string()], // Passages horizontaux
-            vec!["01 00 01".to_string()], // Passages verticaux
-        ];
-        let cells = vec!["000".to_string(), "000".to_string(), "000".to_string()];
+        let exit = start_game_loop(
+            &mut connection,
+            "player_test",
+            &game_state,
+            &config,
+            &mut player_state,
+            &logger,
+            &mut TurnLeft::default(),
+        );
+        assert!(matches!(
+            exit,
+            GameLoopExit::Disconnected {
+                error: FrameError::Io(_),
+                confirmed_moves: 0
+            }
+        ));
+        // L'état est conservé pour la reprise sur une nouvelle connexion.
+        assert_eq!(player_state.position, Position::new(2, -1));
+        assert_eq!(player_state.orientation, Orientation::East);
+    }
+
+    #[test]
+    fn test_strategy_registry() {
+        let mut registry = StrategyRegistry::default();
+        assert_eq!(
+            registry.names().collect::<Vec<_>>(),
+            vec![
+                "compass",
+                "frontier",
+                "left_hand",
+                "pledge",
+                "random",
+                "right_hand",
+                "tremaux"
+            ]
+        );
+        assert!(registry.create("tremaux").is_ok());
+
+        let error = registry.create("teleport").err().unwrap();
+        assert_eq!(
+            error,
+            ConfigError::UnknownNavigationMode {
+                mode: "teleport".to_string(),
+                available: registry.names().map(str::to_string).collect(),
+            }
+        );
+        let mut config = test_config("127.0.0.1:0");
+        config.navigation_mode = "teleport".to_string();
+        assert!(config.validate(&registry).is_err());
+
+        registry.register("teleport", || Box::<TurnLeft>::default());
+        assert!(config.validate(&registry).is_ok());
+    }
+
+    #[test]
+    fn test_game_loop_drives_strategy() {
+        let (listener, server_address) = local_server();
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut connection = FramedStream::new(stream, DEFAULT_MAX_FRAME_SIZE).unwrap();
+            let radar = ServerMessage::RadarView(open_radar().encode());
+            connection.writer.send(&radar).unwrap();
+            let first: ClientMessage = connection.reader.recv().unwrap();
+            connection.writer.send(&radar).unwrap();
+            let second: ClientMessage = connection.reader.recv().unwrap();
+            (first, second)
+        });
+
+        let config = test_config(&server_address);
+        let mut connection =
+            FramedStream::connect(&server_address, DEFAULT_MAX_FRAME_SIZE).unwrap();
+        let game_state = Arc::new(GameState::default());
+        let mut player_state = PlayerState::new(Position::new(0, 0), Orientation::North);
+        let logger = MovementLog {
+            player_name: "player_strategy_test".to_string(),
+        };
+        let mut strategy = TurnLeft::default();
+
+        let exit = start_game_loop(
+            &mut connection,
+            "player_strategy_test",
+            &game_state,
+            &config,
+            &mut player_state,
+            &logger,
+            &mut strategy,
+        );
+        let _ = std::fs::remove_file("player_strategy_test_movements.log");
+
+        assert!(matches!(
+            exit,
+            GameLoopExit::Disconnected {
+                confirmed_moves: 1,
+                ..
+            }
+        ));
+        let expected = ClientMessage::Action(Action::MoveTo(RelativeDirection::Left));
+        assert_eq!(server.join().unwrap(), (expected.clone(), expected));
+        assert_eq!(strategy.radars, 2);
+        assert_eq!(
+            strategy.results,
+            vec![ActionResult::Confirmed(RelativeDirection::Left)]
+        );
+    }
+
+    #[test]
+    fn test_game_loop_reads_messages_while_blocked() {
+        // Cerné par des ennemis : le joueur attend, puis reprend la lecture des messages.
+        let mut surrounded = open_radar();
+        for i in [1, 3, 5, 7] {
+            surrounded.cells[i] = Cell::Known {
+                nature: CellNature::None,
+                entity: CellEntity::Enemy,
+            };
+        }
+        assert_eq!(surrounded.without_allies(), surrounded);
+
+        let (listener, server_address) = local_server();
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut connection = FramedStream::new(stream, DEFAULT_MAX_FRAME_SIZE).unwrap();
+            connection
+                .writer
+                .send(&ServerMessage::RadarView(surrounded.encode()))
+                .unwrap();
+            connection
+                .writer
+                .send(&ServerMessage::Hint(Hint::Secret(5)))
+                .unwrap();
+        });
 
-        let move_direction = tremaux_decide_move(&mut player_state, &radar_data, &cells, "player_test");
+        let config = test_config(&server_address);
+        let mut connection =
+            FramedStream::connect(&server_address, DEFAULT_MAX_FRAME_SIZE).unwrap();
+        let game_state = Arc::new(GameState::default());
+        let mut player_state = PlayerState::new(Position::new(0, 0), Orientation::North);
+        let logger = MovementLog {
+            player_name: "player_blocked_test".to_string(),
+        };
+
+        let exit = start_game_loop(
+            &mut connection,
+            "player_blocked_test",
+            &game_state,
+            &config,
+            &mut player_state,
+            &logger,
+            &mut Tremaux::default(),
+        );
+        server.join().unwrap();
+
+        assert!(matches!(
+            exit,
+            GameLoopExit::Disconnected {
+                confirmed_moves: 0,
+                ..
+            }
+        ));
+        assert_eq!(game_state.secrets.lock().unwrap().len(), 1);
+        assert_eq!(player_state.position, Position::new(0, 0));
+        assert!(player_state.pending_move.is_none());
+    }
+
+    #[test]
+    fn test_retry_backoff() {
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            initial_backoff_ms: 100,
+            max_backoff_ms: 300,
+        };
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(300));
+        assert_eq!(policy.backoff(40), Duration::from_millis(300));
+    }
+
+    #[test]
+    fn test_frame_round_trip() {
+        let mut writer = FrameWriter::new(Vec::new(), DEFAULT_MAX_FRAME_SIZE);
+        writer.send(&ServerMessage::Hint(Hint::Secret(7))).unwrap();
+        writer
+            .send(&ServerMessage::RadarView("abc".to_string()))
+            .unwrap();
+
+        let bytes = writer.get_ref().clone();
+        assert_eq!(
+            &bytes[..4],
+            &(r#"{"Hint":{"Secret":7}}"#.len() as u32).to_le_bytes()
+        );
+
+        let mut reader = FrameReader::new(Cursor::new(bytes), DEFAULT_MAX_FRAME_SIZE);
+        assert_eq!(
+            reader.recv::<ServerMessage>().unwrap(),
+            ServerMessage::Hint(Hint::Secret(7))
+        );
+        assert_eq!(
+            reader.recv::<ServerMessage>().unwrap(),
+            ServerMessage::RadarView("abc".to_string())
+        );
+        assert!(matches!(
+            reader.recv::<ServerMessage>(),
+            Err(FrameError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_frame_size_limits() {
+        // Une taille annoncée de 4 Gio ne doit pas être allouée.
+        let mut reader = FrameReader::new(Cursor::new(u32::MAX.to_le_bytes().to_vec()), 1024);
+        assert!(matches!(
+            reader.read_frame(),
+            Err(FrameError::TooLarge { size, max: 1024 }) if size == u32::MAX as usize
+        ));
+
+        let mut writer = FrameWriter::new(Vec::new(), 8);
+        let result = writer.send(&ServerMessage::RadarView("zveKvsuL8a8aaaa".to_string()));
+        assert!(matches!(result, Err(FrameError::TooLarge { max: 8, .. })));
+        assert!(writer.get_ref().is_empty());
+    }
+
+    #[test]
+    fn test_frame_invalid_json() {
+        let mut bytes = 8u32.to_le_bytes().to_vec();
+        bytes.extend(b"not json");
+        let mut reader = FrameReader::new(Cursor::new(bytes), DEFAULT_MAX_FRAME_SIZE);
+        match reader.recv::<ServerMessage>() {
+            Err(FrameError::Deserialize { frame, .. }) => assert_eq!(frame, "not json"),
+            other => panic!("Erreur de désérialisation attendue, obtenu {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_tremaux_decide_move() {
+        let mut player_state = PlayerState::new(Position::new(0, 0), Orientation::North);
+
+        let radar = open_radar();
+
+        let move_direction = decide(&mut Tremaux::default(), &mut player_state, &radar).unwrap();
 
         assert!(
-            move_direction == "Front" || move_direction == "Right",
+            move_direction == RelativeDirection::Front
+                || move_direction == RelativeDirection::Right,
             "Le joueur ne devrait pas reculer immédiatement !"
         );
     }
 
+    #[test]
+    fn test_tremaux_walks_each_passage_at_most_twice() {
+        let open = perfect_maze();
+        let goal = Position::new(2, 1);
+
+        let mut tremaux = Tremaux::default();
+        let mut player_state = PlayerState::new(Position::new(0, 0), Orientation::North);
+        let mut traversals: std::collections::HashMap<Edge, u32> = Default::default();
+        let mut steps = 0;
+        while player_state.position != goal {
+            let radar = maze_radar(&open, goal, &player_state);
+            tremaux.observe_radar(&radar, &player_state);
+            let direction = decide(&mut tremaux, &mut player_state, &radar).unwrap();
+
+            let side = player_state.orientation.rotate(direction);
+            let edge = Edge::new(player_state.position, side);
+            assert!(
+                open.contains(&edge),
+                "Passage fermé emprunté en {:?}",
+                player_state.position
+            );
+            *traversals.entry(edge).or_insert(0) += 1;
+            move_player(&mut player_state, direction);
+            player_state.pending_move = None;
+
+            steps += 1;
+            assert!(steps <= 2 * open.len(), "Exploration trop longue");
+        }
+        assert!(
+            traversals.values().all(|&count| count <= 2),
+            "{:?}",
+            traversals
+        );
+        assert_eq!(tremaux.marks(Position::new(0, 0), Orientation::South), 1);
+        assert_eq!(tremaux.marks(Position::new(0, 1), Orientation::North), 1);
+    }
+
+    #[test]
+    fn test_tremaux_waits_for_blocked_passage() {
+        // Couloir nord-sud : le nord mène à un cul-de-sac, le sud reste inexploré.
+        let mut corridor = open_radar();
+        corridor.vertical[5] = Passage::Wall;
+        corridor.vertical[6] = Passage::Wall;
+        let mut dead_end = corridor.clone();
+        dead_end.horizontal[4] = Passage::Wall;
+
+        let mut tremaux = Tremaux::default();
+        let mut player_state = PlayerState::new(Position::new(0, 0), Orientation::North);
+        for (radar, expected) in [
+            (&corridor, RelativeDirection::Front),
+            (&dead_end, RelativeDirection::Back),
+        ] {
+            tremaux.observe_radar(radar, &player_state);
+            assert_eq!(
+                decide(&mut tremaux, &mut player_state, radar),
+                Some(expected)
+            );
+            move_player(&mut player_state, expected);
+            player_state.pending_move = None;
+        }
+
+        // De retour au départ, face au sud : un allié occupe la cellule inexplorée devant.
+        let mut blocked = corridor.clone();
+        blocked.cells[1] = Cell::Known {
+            nature: CellNature::None,
+            entity: CellEntity::Ally,
+        };
+        tremaux.observe_radar(&blocked, &player_state);
+        assert_eq!(decide(&mut tremaux, &mut player_state, &blocked), None);
+        assert!(!tremaux.finished(), "Le passage sud reste à explorer");
+
+        tremaux.observe_radar(&corridor, &player_state);
+        assert_eq!(
+            decide(&mut tremaux, &mut player_state, &corridor),
+            Some(RelativeDirection::Front)
+        );
+    }
+
+    #[test]
+    fn test_planner_shortest_paths() {
+        let goal = Position::new(2, 1);
+        let map = known_map(&perfect_maze(), goal);
+        let start = Position::new(0, 0);
+
+        let path = shortest_path(&map, start, goal).unwrap();
+        let expected: Vec<Position> = [(0, 0), (0, 1), (0, 2), (1, 2), (2, 2), (2, 1)]
+            .into_iter()
+            .map(|(x, y)| Position::new(x, y))
+            .collect();
+        assert_eq!(path, expected);
+        assert_eq!(
+            a_star(&map, start, &HashSet::new(), goal).unwrap(),
+            expected
+        );
+        assert_eq!(
+            to_relative_moves(&path, Orientation::North).unwrap(),
+            vec![
+                RelativeDirection::Back,
+                RelativeDirection::Front,
+                RelativeDirection::Left,
+                RelativeDirection::Front,
+                RelativeDirection::Left,
+            ]
+        );
+
+        assert_eq!(shortest_path(&map, start, start).unwrap(), vec![start]);
+        assert!(shortest_path(&map, start, Position::new(5, 5)).is_none());
+        assert!(a_star(&map, start, &HashSet::new(), Position::new(5, 5)).is_none());
+        let hint_path = bfs(&map, start, |position| position.x() == 2).unwrap();
+        assert_eq!(hint_path.last(), Some(&Position::new(2, 0)));
+        assert!(to_relative_moves(&[start, Position::new(2, 0)], Orientation::North).is_none());
+
+        // Sans murs, plusieurs chemins existent : A* et le parcours en largeur ont la même longueur.
+        let mut open_map = MazeMap::default();
+        open_map.integrate(&open_radar(), Position::new(1, 1), Orientation::East);
+        let corner = Position::new(2, 2);
+        assert_eq!(shortest_path(&open_map, start, corner).unwrap().len(), 5);
+        assert_eq!(
+            a_star(&open_map, start, &HashSet::new(), corner)
+                .unwrap()
+                .len(),
+            5
+        );
+
+        // Cellules bloquées : A* les contourne, ou échoue s'il n'y a pas d'autre passage.
+        let blocked: HashSet<Position> = [Position::new(0, 1), Position::new(1, 1)]
+            .into_iter()
+            .collect();
+        let detour = a_star(&open_map, start, &blocked, corner).unwrap();
+        assert_eq!(detour.len(), 5);
+        assert!(detour.iter().all(|cell| !blocked.contains(cell)));
+        let walled: HashSet<Position> = [Position::new(0, 1)].into_iter().collect();
+        assert!(a_star(&map, start, &walled, goal).is_none());
+    }
+
+    #[test]
+    fn test_frontier_explores_whole_maze_then_stops() {
+        let open = perfect_maze();
+        let nowhere = Position::new(9, 9);
+        let game_state = GameState::default();
+        let mut frontier = StrategyRegistry::default().create("frontier").unwrap();
+        let mut player_state = PlayerState::new(Position::new(0, 0), Orientation::North);
+
+        let mut steps = 0;
+        loop {
+            let radar = maze_radar(&open, nowhere, &player_state);
+            game_state.integrate_radar("player_test", &radar, &player_state);
+            let mut context = NavigationContext {
+                player_name: "player_test",
+                player_state: &mut player_state,
+                game_state: &game_state,
+            };
+            let Some(direction) = frontier.decide(&radar, &mut context) else {
+                break;
+            };
+
+            let side = player_state.orientation.rotate(direction);
+            assert!(open.contains(&Edge::new(player_state.position, side)));
+            move_player(&mut player_state, direction);
+            player_state.pending_move = None;
+            steps += 1;
+            assert!(steps <= 2 * open.len(), "Exploration trop longue");
+        }
+
+        let maps = game_state.map.lock().unwrap();
+        let (map, _) = maps.view("player_test").unwrap();
+        assert_eq!(map.known_cells(), 9);
+        assert_eq!(map.frontiers().count(), 0);
+    }
+
+    #[test]
+    fn test_frontier_waits_behind_blocked_cells() {
+        // Murs devant et à droite ; à gauche une impasse ; derrière, un coéquipier barre le
+        // seul accès aux frontières.
+        let mut radar = open_radar();
+        radar.horizontal[4] = Passage::Wall;
+        radar.vertical[6] = Passage::Wall;
+        radar.horizontal[3] = Passage::Wall;
+        radar.horizontal[6] = Passage::Wall;
+        radar.vertical[4] = Passage::Wall;
+        radar.cells[7] = Cell::Known {
+            nature: CellNature::None,
+            entity: CellEntity::Ally,
+        };
+        let mut crowded = radar.clone();
+        crowded.cells[3] = radar.cells[7];
+        let mut closed = open_radar();
+        closed.horizontal = [Passage::Wall; 12];
+        closed.vertical = [Passage::Wall; 12];
+        // Des frontières autour, mais aucun passage ne sort de la cellule du joueur.
+        let mut walled_in = open_radar();
+        walled_in.horizontal[4] = Passage::Wall;
+        walled_in.horizontal[7] = Passage::Wall;
+        walled_in.vertical[5] = Passage::Wall;
+        walled_in.vertical[6] = Passage::Wall;
+
+        let registry = StrategyRegistry::default();
+        for mode in ["frontier", "compass"] {
+            let game_state = GameState::default();
+            let mut player_state = PlayerState::new(Position::new(1, 1), Orientation::North);
+            game_state.integrate_radar("player_test", &radar, &player_state);
+            assert!(game_state.frontier_reachable("player_test", player_state.position));
+
+            // Les frontières restent atteignables : pas de côté dans l'impasse au lieu de s'arrêter.
+            let mut strategy = registry.create(mode).unwrap();
+            let mut context = NavigationContext {
+                player_name: "player_test",
+                player_state: &mut player_state,
+                game_state: &game_state,
+            };
+            assert_eq!(
+                strategy.decide(&radar, &mut context),
+                Some(RelativeDirection::Left),
+                "{}",
+                mode
+            );
+            assert!(!strategy.finished());
+
+            // Impasse elle aussi occupée : le joueur attend.
+            assert_eq!(strategy.decide(&crowded, &mut context), None, "{}", mode);
+            assert!(!strategy.finished());
+
+            // Plus aucune frontière, ou aucune atteignable : la stratégie s'arrête.
+            for unreachable in [&closed, &walled_in] {
+                let game_state = GameState::default();
+                let mut player_state = PlayerState::new(Position::new(0, 0), Orientation::North);
+                game_state.integrate_radar("player_test", unreachable, &player_state);
+                let mut strategy = registry.create(mode).unwrap();
+                let mut context = NavigationContext {
+                    player_name: "player_test",
+                    player_state: &mut player_state,
+                    game_state: &game_state,
+                };
+                assert_eq!(strategy.decide(unreachable, &mut context), None, "{}", mode);
+                assert!(strategy.finished(), "{}", mode);
+            }
+        }
+    }
+
+    #[test]
+    fn test_wall_followers_and_pledge_reach_goal() {
+        let open = perfect_maze();
+        let goal = Position::new(2, 1);
+        let registry = StrategyRegistry::default();
+
+        // Main gauche : impasse (2, 0), retour, puis impasse (1, 1) avant la sortie.
+        let mut left_hand = registry.create("left_hand").unwrap();
+        assert_eq!(walk(left_hand.as_mut(), &open, goal, 20), Some(11));
+        let mut right_hand = registry.create("right_hand").unwrap();
+        assert!(walk(right_hand.as_mut(), &open, goal, 20).is_some());
+        let mut pledge = registry.create("pledge").unwrap();
+        assert!(walk(pledge.as_mut(), &open, goal, 40).is_some());
+
+        // Contournement d'un mur : demi-tour puis deux virages à droite, soit zéro au total.
+        let mut pledge = Pledge::default();
+        for movement in [
+            RelativeDirection::Back,
+            RelativeDirection::Right,
+            RelativeDirection::Right,
+        ] {
+            pledge.observe_action_result(ActionResult::Confirmed(movement));
+        }
+        assert_eq!(pledge.turns(), 0);
+        pledge.observe_action_result(ActionResult::Rejected {
+            movement: Some(RelativeDirection::Left),
+            error: ActionError::CannotPassThroughWall,
+        });
+        assert_eq!(pledge.turns(), 0);
+    }
+
+    #[test]
+    fn test_coordinator_assigns_distinct_frontiers() {
+        // Vue 3x3 sans murs : les 8 cellules du bord donnent sur l'inconnu.
+        let mut map = MazeMap::default();
+        map.integrate(&open_radar(), Position::new(1, 1), Orientation::North);
+        assert_eq!(map.frontiers().count(), 8);
+
+        let center = Position::new(1, 1);
+        let none = HashSet::new();
+        let mut coordinator = Coordinator::default();
+        let first = coordinator.assign(&map, "player_1", center, &none).unwrap();
+        let second = coordinator.assign(&map, "player_2", center, &none).unwrap();
+        assert_eq!(first.len(), 2);
+        assert_eq!(second.len(), 2);
+        assert_ne!(first.last(), second.last());
+
+        // L'objectif est conservé tant qu'il reste valable.
+        let again = coordinator.assign(&map, "player_1", center, &none).unwrap();
+        assert_eq!(again.last(), first.last());
+
+        // Objectif atteint : une nouvelle frontière, distincte de celle du coéquipier.
+        let reached = *first.last().unwrap();
+        let next = coordinator
+            .assign(&map, "player_1", reached, &none)
+            .unwrap();
+        assert_ne!(next.last(), Some(&reached));
+        assert_ne!(next.last(), second.last());
+
+        // Objectif devenu inaccessible : réattribution.
+        let target = coordinator.target("player_2").unwrap();
+        let blocked: HashSet<Position> = [target].into_iter().collect();
+        let rerouted = coordinator
+            .assign(&map, "player_2", center, &blocked)
+            .unwrap();
+        assert_ne!(rerouted.last(), Some(&target));
+
+        assert!(coordinator.release("player_2").is_some());
+        assert!(coordinator.target("player_2").is_none());
+    }
+
+    #[test]
+    fn test_exit_routing_overrides_exploration() {
+        // Départ commun : la carte est partagée sans attendre l'indice GridSize.
+        let game_state = GameState::default();
+        let mut player_state = PlayerState::new(Position::new(0, 0), Orientation::North);
+        let mut strategy = ExitRouting::new(Box::<TurnLeft>::default());
+
+        // Sortie inconnue : la stratégie d'exploration décide.
+        let radar = open_radar();
+        game_state.integrate_radar("player_test", &radar, &player_state);
+        let mut context = NavigationContext {
+            player_name: "player_test",
+            player_state: &mut player_state,
+            game_state: &game_state,
+        };
+        assert_eq!(
+            strategy.decide(&radar, &mut context),
+            Some(RelativeDirection::Left)
+        );
+
+        // Un coéquipier voit la sortie, en diagonale devant à droite du joueur.
+        let mut teammate_radar = open_radar();
+        teammate_radar.cells[2] = Cell::Known {
+            nature: CellNature::Goal,
+            entity: CellEntity::None,
+        };
+        let teammate = PlayerState::new(Position::new(0, 0), Orientation::North);
+        assert_eq!(
+            game_state.integrate_radar("player_2", &teammate_radar, &teammate),
+            Some(Position::new(1, -1))
+        );
+
+        let mut context = NavigationContext {
+            player_name: "player_test",
+            player_state: &mut player_state,
+            game_state: &game_state,
+        };
+        assert_eq!(
+            strategy.decide(&radar, &mut context),
+            Some(RelativeDirection::Front)
+        );
+
+        // Chemin direct bloqué par un joueur : détour par la droite.
+        let mut crowded = open_radar();
+        crowded.cells[1] = Cell::Known {
+            nature: CellNature::None,
+            entity: CellEntity::Ally,
+        };
+        let mut context = NavigationContext {
+            player_name: "player_test",
+            player_state: &mut player_state,
+            game_state: &game_state,
+        };
+        assert_eq!(
+            strategy.decide(&crowded, &mut context),
+            Some(RelativeDirection::Right)
+        );
+    }
+
+    #[test]
+    fn test_compass_steers_frontier_choice() {
+        assert_eq!(absolute_bearing(Orientation::East, 90.0), 180.0);
+        assert_eq!(absolute_bearing(Orientation::North, -45.0), 315.0);
+        let (dx, dy) = bearing_vector(absolute_bearing(Orientation::West, 180.0));
+        assert!((dx - 1.0).abs() < 1e-9 && dy.abs() < 1e-9);
+
+        // Joueur au centre d'une vue ouverte, face à l'est : huit frontières autour de lui.
+        let game_state = GameState::default();
+        let mut player_state = PlayerState::new(Position::new(1, 1), Orientation::East);
+        let radar = open_radar();
+        game_state.integrate_radar("player_test", &radar, &player_state);
+        let mut compass = Compass::default();
+
+        // Sans boussole : frontière la plus proche, (0, 1) derrière le joueur.
+        let mut context = NavigationContext {
+            player_name: "player_test",
+            player_state: &mut player_state,
+            game_state: &game_state,
+        };
+        assert_eq!(
+            compass.decide(&radar, &mut context),
+            Some(RelativeDirection::Back)
+        );
+
+        // Le déplacement en attente n'est pas encore pris en compte par le serveur :
+        // l'angle est relatif à l'orientation confirmée (est), soit un cap plein nord.
+        move_player(&mut player_state, RelativeDirection::Back);
+        assert_eq!(player_state.confirmed_orientation(), Orientation::East);
+        compass.observe_hint(&Hint::RelativeCompass { angle: -90.0 }, &player_state);
+        player_state.rollback_move();
+
+        let mut context = NavigationContext {
+            player_name: "player_test",
+            player_state: &mut player_state,
+            game_state: &game_state,
+        };
+        assert_eq!(
+            compass.decide(&radar, &mut context),
+            Some(RelativeDirection::Left)
+        );
+    }
+
+    #[test]
+    fn test_triangulation_from_several_players() {
+        let reading = |player_name: &str, x, y, bearing| CompassReading {
+            player_name: player_name.to_string(),
+            position: Position::new(x, y),
+            bearing,
+        };
+        let mut triangulation = Triangulation::default();
+
+        // Un seul relevé, ou des relevés parallèles, ne suffisent pas.
+        assert!(triangulation
+            .record(reading("player_1", 0, 0, 90.0))
+            .is_none());
+        assert!(triangulation
+            .record(reading("player_1", 0, 0, 90.0))
+            .is_none());
+        assert!(triangulation
+            .record(reading("player_1", 0, 2, 90.0))
+            .is_none());
+
+        // La sortie est en (4, 1) : à l'est des deux premiers, au nord-ouest du troisième.
+        let estimate = triangulation
+            .record(reading("player_2", 5, 2, 315.0))
+            .unwrap();
+        assert_eq!(estimate.cell(), Position::new(4, 1));
+        assert_eq!(estimate.readings, 3);
+
+        // Un relevé exact de plus améliore la précision.
+        let better = triangulation
+            .record(reading("player_3", 4, 5, 0.0))
+            .unwrap();
+        assert_eq!(better.cell(), Position::new(4, 1));
+        assert!(better.residual < estimate.residual);
+
+        // Partagé par l'équipe : conversion de l'angle relatif selon l'orientation du joueur.
+        let game_state = GameState::default();
+        let east = PlayerState::new(Position::new(0, 1), Orientation::East);
+        let north = PlayerState::new(Position::new(4, 5), Orientation::North);
+        assert!(game_state.record_compass("player_1", &east, 0.0).is_none());
+        game_state.record_compass("player_2", &north, 0.0).unwrap();
+        assert_eq!(
+            game_state.estimated_goal("player_1").unwrap().cell(),
+            Position::new(4, 1)
+        );
+    }
+
+    #[test]
+    fn test_grid_size_bounds_exploration() {
+        // Vue 3x3 sans murs autour de (1, 1) : huit frontières tant que la taille est inconnue.
+        let mut map = MazeMap::default();
+        map.integrate(&open_radar(), Position::new(1, 1), Orientation::North);
+        assert_eq!(map.frontiers().count(), 8);
+        assert!(!map.is_exhausted());
+
+        // Cinq colonnes sur trois lignes : seules les colonnes du bord peuvent encore s'étendre.
+        assert!(map.set_grid_size(GridSize {
+            columns: 5,
+            rows: 3
+        }));
+        assert!(!map.set_grid_size(GridSize {
+            columns: 5,
+            rows: 3
+        }));
+        assert!(map.may_contain(Position::new(-2, 1)));
+        assert!(!map.may_contain(Position::new(1, -1)));
+        assert_eq!(map.frontiers().count(), 6);
+        assert_eq!(map.grid_origin(), None);
+
+        // Grille 3x3 : tout est vu, l'origine est connue et il n'y a pas de sortie.
+        let mut map = MazeMap::default();
+        map.integrate(&open_radar(), Position::new(4, -2), Orientation::East);
+        map.set_grid_size(GridSize {
+            columns: 3,
+            rows: 3,
+        });
+        assert_eq!(map.grid_origin(), Some(Position::new(3, -3)));
+        assert_eq!(map.to_grid(Position::new(5, -1)), Some(Position::new(2, 2)));
+        assert_eq!(map.frontiers().count(), 0);
+        assert!(map.is_exhausted());
+
+        // Labyrinthe parfait entièrement exploré : sortie trouvée, donc pas épuisé.
+        let map = known_map(&perfect_maze(), Position::new(2, 1));
+        assert!(!map.is_exhausted());
+    }
+
+    #[test]
+    fn test_sos_sends_nearest_teammates() {
+        let open = perfect_maze();
+        let goal = Position::new(9, 9);
+        let game_state = GameState::default();
+        for x in 0..3 {
+            for y in 0..3 {
+                let player_state = PlayerState::new(Position::new(x, y), Orientation::North);
+                game_state.integrate_radar(
+                    "player_1",
+                    &maze_radar(&open, goal, &player_state),
+                    &player_state,
+                );
+            }
+        }
+        for (name, x, y) in [("player_1", 0, 0), ("player_2", 2, 1), ("player_3", 2, 0)] {
+            game_state.update_player_position(name, Position::new(x, y));
+        }
+
+        // Distances jusqu'à (1, 2) : 3 pas pour player_1, 2 pour player_2, 5 pour player_3.
+        let rescuers = game_state.raise_sos("player_4", Position::new(1, 2));
+        assert_eq!(
+            rescuers,
+            vec!["player_2".to_string(), "player_1".to_string()]
+        );
+        assert!(game_state
+            .rescue_path("player_3", Position::new(2, 0), &HashSet::new())
+            .is_none());
+        assert_eq!(
+            game_state.volunteer_rescue("player_3"),
+            Some(("player_4".to_string(), Position::new(1, 2)))
+        );
+
+        // player_2, orienté au nord, part vers le sud ; le coéquipier ne bloque pas son chemin.
+        let mut player_state = PlayerState::new(Position::new(2, 1), Orientation::North);
+        let mut strategy = RescueRouting::new(Box::<TurnLeft>::default());
+        let radar = maze_radar(&open, goal, &player_state);
+        let blocked: HashSet<Position> = [Position::new(1, 2)].into_iter().collect();
+        let (_, path) = game_state
+            .rescue_path("player_2", player_state.position, &blocked)
+            .unwrap();
+        assert_eq!(path.len(), 3);
+        let mut context = NavigationContext {
+            player_name: "player_2",
+            player_state: &mut player_state,
+            game_state: &game_state,
+        };
+        assert_eq!(
+            strategy.decide(&radar, &mut context),
+            Some(RelativeDirection::Back)
+        );
+
+        // Arrivé sur la cellule du coéquipier : appel clos, la stratégie enveloppée reprend.
+        player_state.position = Position::new(1, 2);
+        let radar = maze_radar(&open, goal, &player_state);
+        let mut context = NavigationContext {
+            player_name: "player_2",
+            player_state: &mut player_state,
+            game_state: &game_state,
+        };
+        assert_eq!(
+            strategy.decide(&radar, &mut context),
+            Some(RelativeDirection::Left)
+        );
+        assert!(game_state
+            .rescue_path("player_1", Position::new(0, 0), &HashSet::new())
+            .is_none());
+        assert!(!game_state.resolve_sos("player_4"));
+
+        // Un joueur qui a quitté la partie n'est plus choisi comme secouriste.
+        game_state.release_player("player_2");
+        let rescuers = game_state.raise_sos("player_4", Position::new(1, 2));
+        assert_eq!(
+            rescuers,
+            vec!["player_1".to_string(), "player_3".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_players_share_map_once_aligned_on_grid() {
+        // player_1 démarre en (0, 0) de la grille, player_2 en (2, 1) ; la sortie est en (2, 2).
+        let open = perfect_maze();
+        let goal = Position::new(2, 2);
+        let (spawn_1, spawn_2) = (Position::new(0, 0), Position::new(2, 1));
+        let game_state = GameState::new(true);
+        let view = |player_name: &str, grid: Position, spawn: Position| {
+            let radar = maze_radar(&open, goal, &PlayerState::new(grid, Orientation::North));
+            game_state.integrate_radar(
+                player_name,
+                &radar,
+                &PlayerState::new(grid - spawn, Orientation::North),
+            )
+        };
+        let north = PlayerState::new(Position::new(0, 0), Orientation::North);
+        let origin = Position::new(0, 0);
+
+        // Chacun sa carte, dans son repère : rien n'est partagé tant que les départs sont inconnus.
+        assert_eq!(
+            view("player_2", spawn_2, spawn_2),
+            Some(Position::new(0, 1))
+        );
+        assert_eq!(view("player_1", spawn_1, spawn_1), None);
+        assert!(game_state
+            .exit_path("player_1", origin, &HashSet::new())
+            .is_none());
+        assert_eq!(
+            game_state.exit_path("player_2", origin, &HashSet::new()),
+            Some(vec![origin, Position::new(0, 1)])
+        );
+        assert!(game_state.raise_sos("player_1", origin).is_empty());
+        assert!(game_state
+            .record_compass("player_1", &north, 135.0)
+            .is_none());
+        assert!(game_state
+            .record_compass("player_2", &north, 180.0)
+            .is_none());
+        assert!(game_state.estimated_goal("player_1").is_none());
+
+        // Une fois la taille connue, chaque carte qui couvre toute la grille la situe.
+        assert!(game_state.set_grid_size(GridSize {
+            columns: 3,
+            rows: 3
+        }));
+        view("player_1", Position::new(1, 0), spawn_1);
+        view("player_1", Position::new(0, 1), spawn_1);
+        assert_eq!(
+            game_state
+                .map
+                .lock()
+                .unwrap()
+                .frame("player_1")
+                .unwrap()
+                .offset(),
+            origin
+        );
+        assert!(game_state
+            .exit_path("player_1", origin, &HashSet::new())
+            .is_none());
+
+        view("player_2", Position::new(1, 1), spawn_2);
+        assert_eq!(
+            game_state
+                .map
+                .lock()
+                .unwrap()
+                .frame("player_2")
+                .unwrap()
+                .offset(),
+            spawn_2
+        );
+
+        // La sortie vue par player_2 est maintenant sur la carte de player_1, qui ne l'a jamais vue.
+        let path = game_state
+            .exit_path("player_1", origin, &HashSet::new())
+            .unwrap();
+        assert_eq!(path.len(), 5);
+        assert_eq!(path.last(), Some(&goal));
+        assert_eq!(
+            game_state.exit_path("player_2", origin, &HashSet::new()),
+            Some(vec![origin, Position::new(0, 1)])
+        );
+
+        // Les boussoles reçues avant l'alignement se croisent sur la sortie.
+        assert_eq!(game_state.estimated_goal("player_1").unwrap().cell(), goal);
+        assert_eq!(
+            game_state.estimated_goal("player_2").unwrap().cell(),
+            Position::new(0, 1)
+        );
+
+        // Les positions des secours sont converties d'un repère à l'autre.
+        game_state.update_player_position("player_2", origin);
+        assert_eq!(
+            game_state.raise_sos("player_1", origin),
+            vec!["player_2".to_string()]
+        );
+        let (_, path) = game_state
+            .rescue_path("player_2", origin, &HashSet::new())
+            .unwrap();
+        assert_eq!(path.last(), Some(&(spawn_1 - spawn_2)));
+    }
+
+    #[test]
+    fn test_challenge_solvers() {
+        let (listener, server_address) = local_server();
+        let stream = TcpStream::connect(server_address).expect("Échec de connexion");
+        let mut writer = FrameWriter::new(stream, DEFAULT_MAX_FRAME_SIZE);
+        let (server_side, _) = listener.accept().expect("Aucun client accepté");
+        let mut reader = FrameReader::new(server_side, DEFAULT_MAX_FRAME_SIZE);
+
+        let config = test_config("127.0.0.1:0");
+        let game_state = Arc::new(GameState::default());
+        game_state.register_player("player_1");
+        game_state.record_secret("player_1", 17);
+        let player_state = PlayerState::new(Position::new(3, 4), Orientation::North);
+        let mut solvers = ChallengeSolvers::default();
+        assert_eq!(
+            solvers.kinds().collect::<Vec<_>>(),
+            vec!["SOS", "SecretSumModulo"]
+        );
+
+        let mut context = ChallengeContext {
+            player_name: "player_1",
+            player_state: &player_state,
+            game_state: &game_state,
+            config: &config,
+            writer: &mut writer,
+        };
+        assert!(solvers.solve(&Challenge::SecretSumModulo(5), &mut context));
+        assert_eq!(
+            reader.recv::<ClientMessage>().unwrap(),
+            ClientMessage::Action(Action::SolveChallenge {
+                answer: "2".to_string()
+            })
+        );
+
+        // SOS : l'appel est diffusé, puis clos quand le joueur peut de nouveau se déplacer.
+        assert!(solvers.solve(&Challenge::SOS, &mut context));
+        assert!(game_state.rescue.lock().unwrap().is_in_distress("player_1"));
+        solvers.finish(&mut context);
+        assert!(!game_state.rescue.lock().unwrap().is_in_distress("player_1"));
+
+        // Challenge inconnu : aucun solveur, le joueur le signale et le compte.
+        let unknown = Challenge::Unsupported("Riddle".to_string());
+        assert!(!solvers.solve(&unknown, &mut context));
+        assert!(!solvers.solve(&Challenge::Unsupported("SOS".to_string()), &mut context));
+        assert!(!game_state.rescue.lock().unwrap().is_in_distress("player_1"));
+        assert_eq!(game_state.record_unsupported_challenge(unknown.kind()), 1);
+        assert_eq!(game_state.record_unsupported_challenge(unknown.kind()), 2);
+    }
+
+    #[test]
+    fn test_client_message_format() {
+        let action = ClientMessage::Action(Action::SolveChallenge {
+            answer: "42".to_string(),
+        });
+        assert_eq!(
+            serde_json::to_string(&action).unwrap(),
+            r#"{"Action":{"SolveChallenge":{"answer":"42"}}}"#
+        );
+
+        let messages = vec![
+            action,
+            ClientMessage::Action(Action::MoveTo(RelativeDirection::Left)),
+            ClientMessage::SubscribePlayer(crate::protocol::SubscribePlayer {
+                name: "player_1".to_string(),
+                registration_token: "abcd1234".to_string(),
+            }),
+        ];
+        for message in messages {
+            let json = serde_json::to_string(&message).unwrap();
+            let decoded: ClientMessage = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded, message);
+        }
+    }
+
+    #[test]
+    fn test_parse_server_messages() {
+        let cases = vec![
+            (
+                r#"{"RegisterTeamResult":{"Ok":{"expected_players":3,"registration_token":"abc"}}}"#,
+                ServerMessage::RegisterTeamResult(RegisterTeamResult::Ok {
+                    expected_players: 3,
+                    registration_token: "abc".to_string(),
+                }),
+            ),
+            (
+                r#"{"SubscribePlayerResult":{"Err":"InvalidRegistrationToken"}}"#,
+                ServerMessage::SubscribePlayerResult(SubscribePlayerResult::Err(
+                    RegistrationError::InvalidRegistrationToken,
+                )),
+            ),
+            (
+                r#"{"SubscribePlayerResult":"Ok"}"#,
+                ServerMessage::SubscribePlayerResult(SubscribePlayerResult::Ok),
+            ),
+            (
+                r#"{"Hint":{"RelativeCompass":{"angle":12.5}}}"#,
+                ServerMessage::Hint(Hint::RelativeCompass { angle: 12.5 }),
+            ),
+            (
+                r#"{"Hint":{"GridSize":{"columns":10,"rows":7}}}"#,
+                ServerMessage::Hint(Hint::GridSize {
+                    columns: 10,
+                    rows: 7,
+                }),
+            ),
+            (
+                r#"{"Hint":{"Secret":17}}"#,
+                ServerMessage::Hint(Hint::Secret(17)),
+            ),
+            (
+                r#"{"Hint":"SOSHelper"}"#,
+                ServerMessage::Hint(Hint::SOSHelper),
+            ),
+            (
+                r#"{"Challenge":{"SecretSumModulo":100}}"#,
+                ServerMessage::Challenge(Challenge::SecretSumModulo(100)),
+            ),
+            (
+                r#"{"Challenge":"SOS"}"#,
+                ServerMessage::Challenge(Challenge::SOS),
+            ),
+            (
+                r#"{"ActionError":"CannotPassThroughWall"}"#,
+                ServerMessage::ActionError(ActionError::CannotPassThroughWall),
+            ),
+            (
+                r#"{"RadarView":"zveKvsuL8a8aaaa"}"#,
+                ServerMessage::RadarView("zveKvsuL8a8aaaa".to_string()),
+            ),
+        ];
+
+        for (raw, expected) in cases {
+            let parsed = parse_server_message(raw.as_bytes()).unwrap();
+            assert_eq!(parsed, expected);
+            let reparsed = parse_server_message(serde_json::to_string(&parsed).unwrap().as_bytes());
+            assert_eq!(reparsed.unwrap(), expected);
+        }
+    }
 
+    #[test]
+    fn test_parse_unknown_server_message() {
+        assert!(parse_server_message(br#"{"Teleport":{"x":1}}"#).is_err());
+        assert_eq!(
+            parse_server_message(br#"{"Challenge":{"Riddle":{"question":"?"}}}"#),
+            Ok(ServerMessage::Challenge(Challenge::Unsupported(
+                "Riddle".to_string()
+            )))
+        );
+        assert_eq!(
+            parse_server_message(br#"{"Challenge":"Quiz"}"#),
+            Ok(ServerMessage::Challenge(Challenge::Unsupported(
+                "Quiz".to_string()
+            )))
+        );
+        assert!(parse_server_message(br#"{"Challenge":42}"#).is_err());
+        assert!(parse_server_message(br#"{"Challenge":{"SecretSumModulo":"oops"}}"#).is_err());
+        assert!(parse_server_message(br#"{"Challenge":{"SOS":1}}"#).is_err());
+        assert!(parse_server_message(br#"{"ActionError":"Unknown"}"#).is_err());
+        assert!(parse_server_message(b"not json").is_err());
+    }
 }
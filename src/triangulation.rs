@@ -0,0 +1,137 @@
+use crate::direction::bearing_vector;
+use crate::player::Position;
+
+/// Nombre maximal de relevés conservés ; les plus anciens sont oubliés en premier.
+const MAX_READINGS: usize = 64;
+
+/// Déterminant en dessous duquel les relevés sont considérés comme parallèles.
+const MIN_DETERMINANT: f64 = 1e-6;
+
+/// Relevé de boussole : position d'un joueur et cap absolu de la sortie depuis cette position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompassReading {
+    pub player_name: String,
+    pub position: Position,
+    /// Degrés dans le sens horaire à partir du nord
+    pub bearing: f32,
+}
+
+/// Position estimée de la sortie, dans le repère des relevés.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GoalEstimate {
+    pub x: f64,
+    pub y: f64,
+    /// Distance moyenne (en cases) entre l'estimation et les demi-droites des relevés
+    pub residual: f64,
+    /// Nombre de relevés utilisés
+    pub readings: usize,
+}
+
+impl GoalEstimate {
+    /// Cellule la plus proche de l'estimation.
+    pub fn cell(&self) -> Position {
+        Position::new(self.x.round() as i32, self.y.round() as i32)
+    }
+
+    /// La même estimation dans un repère décalé de `offset`.
+    pub fn translated(self, offset: Position) -> Self {
+        Self {
+            x: self.x + offset.x() as f64,
+            y: self.y + offset.y() as f64,
+            ..self
+        }
+    }
+}
+
+/// Triangulation de la sortie à partir des boussoles reçues par tous les joueurs.
+///
+/// Chaque relevé définit une droite passant par la position du joueur dans la direction
+/// indiquée ; l'estimation est le point qui minimise la somme des carrés des distances
+/// à ces droites.
+#[derive(Debug, Default)]
+pub struct Triangulation {
+    readings: Vec<CompassReading>,
+    estimate: Option<GoalEstimate>,
+}
+
+impl Triangulation {
+    /// Ajoute un relevé et recalcule l'estimation.
+    ///
+    /// Retourne la nouvelle estimation lorsqu'elle change de cellule ou devient plus précise.
+    pub fn record(&mut self, reading: CompassReading) -> Option<GoalEstimate> {
+        if self.readings.contains(&reading) {
+            return None;
+        }
+        if self.readings.len() == MAX_READINGS {
+            self.readings.remove(0);
+        }
+        self.readings.push(reading);
+
+        let estimate = least_squares(&self.readings)?;
+        let improved = match self.estimate {
+            Some(previous) => {
+                previous.cell() != estimate.cell() || estimate.residual < previous.residual
+            }
+            None => true,
+        };
+        self.estimate = Some(estimate);
+        improved.then_some(estimate)
+    }
+
+    /// Estimation courante, dès que deux relevés non parallèles sont disponibles.
+    pub fn estimate(&self) -> Option<GoalEstimate> {
+        self.estimate
+    }
+
+    pub fn readings(&self) -> &[CompassReading] {
+        &self.readings
+    }
+}
+
+/// Point le plus proche, au sens des moindres carrés, des droites des relevés.
+fn least_squares(readings: &[CompassReading]) -> Option<GoalEstimate> {
+    // Somme des projecteurs orthogonaux I - d·dᵀ et de leurs images des positions
+    let (mut a11, mut a12, mut a22, mut b1, mut b2) = (0.0, 0.0, 0.0, 0.0, 0.0);
+    for reading in readings {
+        let (dx, dy) = bearing_vector(reading.bearing);
+        let (px, py) = (reading.position.x() as f64, reading.position.y() as f64);
+        let (p11, p12, p22) = (1.0 - dx * dx, -dx * dy, 1.0 - dy * dy);
+        a11 += p11;
+        a12 += p12;
+        a22 += p22;
+        b1 += p11 * px + p12 * py;
+        b2 += p12 * px + p22 * py;
+    }
+
+    let determinant = a11 * a22 - a12 * a12;
+    if determinant.abs() < MIN_DETERMINANT {
+        return None;
+    }
+    let x = (a22 * b1 - a12 * b2) / determinant;
+    let y = (a11 * b2 - a12 * b1) / determinant;
+
+    let residual = readings
+        .iter()
+        .map(|reading| {
+            let (dx, dy) = bearing_vector(reading.bearing);
+            let (vx, vy) = (
+                x - reading.position.x() as f64,
+                y - reading.position.y() as f64,
+            );
+            // Derrière le joueur, la distance est celle à son origine
+            if vx * dx + vy * dy < 0.0 {
+                vx.hypot(vy)
+            } else {
+                (vx * dy - vy * dx).abs()
+            }
+        })
+        .sum::<f64>()
+        / readings.len() as f64;
+
+    Some(GoalEstimate {
+        x,
+        y,
+        residual,
+        readings: readings.len(),
+    })
+}
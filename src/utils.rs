@@ -1,71 +1,86 @@
-use serde::{Deserialize, Serialize};
+use std::fmt;
 
-// Types pour les messages serveur
-#[derive(Serialize, Deserialize, Debug)]
-#[serde(rename_all = "PascalCase")]
-#[allow(non_snake_case)]
-#[allow(clippy::upper_case_acronyms)]
-pub enum ServerMessage {
-    RadarView { RadarView: String },
-    Challenge { Challenge: ChallengeType },
+/// Alphabet base64 du jeu : minuscules d'abord, puis majuscules, chiffres, `+` et `/`.
+const B64_ALPHABET: &[u8; 64] =
+    b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789+/";
+
+/// Erreurs de décodage base64.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum B64Error {
+    /// Caractère absent de l'alphabet du jeu.
+    InvalidCharacter { character: char, position: usize },
+    /// Une longueur de la forme `4n + 1` ne peut correspondre à aucune suite d'octets.
+    InvalidLength(usize),
+    /// Les bits de remplissage du dernier caractère ne sont pas nuls.
+    NonZeroTrailingBits,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-#[allow(clippy::upper_case_acronyms)]
-pub enum ChallengeType {
-    SecretSumModulo(u64),
-    SOS,
+impl fmt::Display for B64Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            B64Error::InvalidCharacter { character, position } => write!(
+                f,
+                "Caractère non valide dans l'encodage b64 : {} (position {})",
+                character, position
+            ),
+            B64Error::InvalidLength(length) => {
+                write!(f, "Longueur d'encodage b64 invalide : {}", length)
+            }
+            B64Error::NonZeroTrailingBits => {
+                write!(f, "Bits de remplissage non nuls en fin d'encodage b64")
+            }
+        }
+    }
 }
 
-pub fn decode_b64(encoded: &str) -> Result<String, String> {
-    let b64_alphabet: Vec<char> =
-        "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789+/"
-            .chars()
-            .collect();
+impl std::error::Error for B64Error {}
+
+/// Encode des octets en base64 avec l'alphabet du jeu, sans caractère de remplissage.
+pub fn encode_b64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let mut group = [0u8; 3];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let value = u32::from_be_bytes([0, group[0], group[1], group[2]]);
 
-    let mut bits = String::new();
-    for char in encoded.chars() {
-        if let Some(index) = b64_alphabet.iter().position(|&c| c == char) {
-            bits.push_str(&format!("{:06b}", index));
-        } else {
-            return Err(format!(
-                "Caractère non valide dans l'encodage b64 : {}",
-                char
-            ));
+        // 1 octet -> 2 caractères, 2 octets -> 3 caractères, 3 octets -> 4 caractères
+        for i in 0..=chunk.len() {
+            let index = (value >> (18 - 6 * i)) & 0x3F;
+            encoded.push(B64_ALPHABET[index as usize] as char);
         }
     }
+    encoded
+}
 
-    let bytes: Vec<u8> = bits
-        .as_bytes()
-        .chunks(8)
-        .filter_map(|chunk| {
-            if chunk.len() == 8 {
-                match u8::from_str_radix(&String::from_utf8_lossy(chunk), 2) {
-                    Ok(byte) => Some(byte),
-                    Err(e) => {
-                        eprintln!("[DEBUG] Erreur de conversion des bits en u8 : {}", e);
-                        None
-                    }
-                }
-            } else {
-                None
-            }
-        })
-        .collect();
+/// Décode une chaîne base64 (alphabet du jeu, sans remplissage) en octets.
+///
+/// Les bits restants après le dernier octet complet doivent être nuls.
+pub fn decode_b64(encoded: &str) -> Result<Vec<u8>, B64Error> {
+    let length = encoded.chars().count();
+    if length % 4 == 1 {
+        return Err(B64Error::InvalidLength(length));
+    }
 
+    let mut bytes = Vec::with_capacity(length * 3 / 4);
+    let mut buffer: u32 = 0;
+    let mut bit_count = 0;
+    for (position, character) in encoded.chars().enumerate() {
+        let index = B64_ALPHABET
+            .iter()
+            .position(|&c| c as char == character)
+            .ok_or(B64Error::InvalidCharacter { character, position })?;
 
-    let decoded = bytes
-        .iter()
-        .enumerate()
-        .map(|(i, &byte)| {
-            if i < 6 {
-                format!("{:08b}", byte)
-            } else {
-                format!("{:02X}", byte)
-            }
-        })
-        .collect::<Vec<String>>()
-        .join(" ");
+        buffer = (buffer << 6) | index as u32;
+        bit_count += 6;
+        if bit_count >= 8 {
+            bit_count -= 8;
+            bytes.push((buffer >> bit_count) as u8);
+            buffer &= (1 << bit_count) - 1;
+        }
+    }
 
-    Ok(decoded)
+    if buffer != 0 {
+        return Err(B64Error::NonZeroTrailingBits);
+    }
+    Ok(bytes)
 }